@@ -1,3 +1,5 @@
+use crate::traits;
+
 pub type Result<T> = std::result::Result<T, Error>;
 pub type DeriveSqlResult<T> = std::result::Result<T, Error>;
 
@@ -9,6 +11,8 @@ pub enum Error {
   MySQLRowIdNotSupported,
   #[error("Update statement with limit and/or offset is not supported")]
   UpdateWithLimitOffsetNotSupported,
+  #[error("`RETURNING` clause is not supported in SQL flavor `{0}`")]
+  ReturningNotSupported(String),
   #[error("Unable to convert from PostgreSQL type `{0}`")]
   PostgreSQLInvalidConversion(String),
   #[error("Type `{1}` is not supported in SQL flavor `{0}`")]
@@ -23,6 +27,9 @@ pub enum Error {
   InvalidTypeForFrom(String, String),
   #[error("Conversion of SQL value to type `{0}` is invalid")]
   InvalidTypeFor(String),
+  #[error("SQL value `{1}` does not match any variant of enum `{0}`")]
+  UnknownEnumValue(String, String),
+  #[deprecated(note = "Statements accept any number of parameters and no longer return this error")]
   #[error("The maximum number of parameter - `{0}` - has been exceeded. Requested: `{1}`")]
   MaximumNumberOfParametersExceeded(usize, usize),
   #[error("Row item `{0}` not found")]
@@ -37,24 +44,186 @@ pub enum Error {
   MySqlProxyNoConnectionProvided,
   #[error("No SQLite connection provided to Log proxy")]
   SqliteProxyNoConnectionProvided,
+  #[error("Connection URL scheme `{0}` is not supported or its feature is not enabled")]
+  ConnectionUrlNotSupported(String),
+  #[error("Timed out waiting for a connection to be released to the pool")]
+  PoolCheckoutTimeout,
+  #[error("Transaction control is not available on a transaction guard. Use `begin_transaction` to create a nested transaction")]
+  TransactionInProgress,
+  #[error("Migration `{0}` is registered more than once")]
+  MigrationDuplicateVersion(i64),
+  #[error("Migration `{0}` has been applied but is not registered")]
+  MigrationNotRegistered(i64),
+  #[error("Migration `{0}` has been modified since it was applied")]
+  MigrationModified(i64),
+  #[error("Migration `{0}` is pending but a migration with a later version has already been applied")]
+  MigrationOutOfOrder(i64),
+  #[error("Migration `{0}` can not be reverted as it has no down migration")]
+  MigrationIrreversible(i64),
+  #[cfg(feature = "json")]
+  #[error(transparent)]
+  JsonError(#[from] serde_json::Error),
   #[cfg(feature = "mysql")]
   #[error(transparent)]
   MysqlFromValueError(#[from] mysql::FromValueError),
   #[cfg(feature = "mysql")]
   #[error(transparent)]
-  MysqlError(#[from] mysql::Error),
+  MysqlError(mysql::Error),
   #[cfg(feature = "sqlite")]
   #[error(transparent)]
-  RusqliteError(#[from] rusqlite::Error),
+  RusqliteError(rusqlite::Error),
   #[cfg(feature = "postgres")]
   #[error(transparent)]
-  PostgresError(#[from] ::postgres::Error),
+  PostgresError(::postgres::Error),
+  #[error("Statement `{sql}`{params} failed", params = display_params(.params))]
+  Statement { sql: String, params: Option<Vec<traits::Param>>, #[source] source: Box<Error> },
+  #[error("Unique constraint violation")]
+  UniqueViolation(#[source] Box<Error>),
+  #[error("Foreign key constraint violation")]
+  ForeignKeyViolation(#[source] Box<Error>),
+  #[error("Not null constraint violation")]
+  NotNullViolation(#[source] Box<Error>),
+  #[error("Database busy, locked or deadlocked")]
+  Busy(#[source] Box<Error>),
+  #[error("Connection to the database lost")]
+  ConnectionLost(#[source] Box<Error>),
+  #[error("Statement timed out")]
+  Timeout(#[source] Box<Error>),
+  /// SQL syntax error. SQLite does not provide an error code specific to syntax errors such that SQLite errors
+  /// are classified based on the English error message `syntax error`.
+  #[error("SQL syntax error")]
+  SyntaxError(#[source] Box<Error>),
   #[error("Error: {0}")]
   Misc(String),
 }
 
+impl Error {
+  /// Whether the error is transient - ie database busy, connection lost or timeout - such that the
+  /// statement may succeed if retried
+  pub fn is_transient(&self) -> bool {
+    matches!(self.inner(), Error::Busy(_) | Error::ConnectionLost(_) | Error::Timeout(_))
+  }
+
+  /// Error without the statement context, ie the source of an `Error::Statement`
+  pub fn inner(&self) -> &Error {
+    match self {
+      Error::Statement { source, .. } => source.inner(),
+      e => e,
+    }
+  }
+
+  /// Error with the parameters values omitted from the `Error::Statement` context - for example when parameters
+  /// may contain personal information. See `proxy::Log::with_redact_params`.
+  pub fn without_params(self) -> Error {
+    match self {
+      Error::Statement { sql, source, .. } => Error::Statement { sql, params: None, source },
+      e => e,
+    }
+  }
+
+  /// Attach the statement and its parameters to the error. Errors already providing a statement context are returned as is.
+  /// Parameters are omitted when they can not be converted.
+  pub fn from_statement_params_source<P>(sql: &str, params: &P, source: Error) -> Error
+  where P: traits::Params,
+  {
+    match source {
+      Error::Statement { .. } => source,
+      source => Error::Statement { sql: sql.to_string(), params: params.as_vec_params().ok(), source: Box::new(source) },
+    }
+  }
+}
+
+fn display_params(params: &Option<Vec<traits::Param>>) -> String {
+  match params {
+    Some(params) if params.is_empty() => String::new(),
+    Some(params) => format!(" with parameters {params:?}"),
+    None => " with parameters omitted".to_string(),
+  }
+}
+
+/// Attach the statement and its parameters to the error of a result
+pub(crate) trait StatementContext<T> {
+  fn with_statement<P>(self, sql: &str, params: &P) -> Result<T>
+  where P: traits::Params;
+}
+
+impl<T> StatementContext<T> for Result<T> {
+  fn with_statement<P>(self, sql: &str, params: &P) -> Result<T>
+  where P: traits::Params,
+  {
+    self.map_err(|e| Error::from_statement_params_source(sql, params, e))
+  }
+}
+
 impl std::convert::From<&str> for Error {
   fn from(v: &str) -> Self {
     Error::Misc(v.to_string())
   }
 }
+
+/// Driver errors are classified into the normalized variants - ie `UniqueViolation`, `Busy`, etc - with the driver error
+/// kept as source. Errors that can not be classified are returned as the driver variant. Syntax errors are reported
+/// with the generic `SQLITE_ERROR` code and are identified from the error message.
+#[cfg(feature = "sqlite")]
+impl std::convert::From<rusqlite::Error> for Error {
+  fn from(e: rusqlite::Error) -> Self {
+    use rusqlite::ffi;
+    let wrap: Option<fn(Box<Error>) -> Error> = match (e.sqlite_error_code(), e.sqlite_extended_error_code()) {
+      (_, Some(ffi::SQLITE_CONSTRAINT_UNIQUE))
+      | (_, Some(ffi::SQLITE_CONSTRAINT_PRIMARYKEY)) => Some(Error::UniqueViolation),
+      (_, Some(ffi::SQLITE_CONSTRAINT_FOREIGNKEY))   => Some(Error::ForeignKeyViolation),
+      (_, Some(ffi::SQLITE_CONSTRAINT_NOTNULL))      => Some(Error::NotNullViolation),
+      (Some(ffi::ErrorCode::DatabaseBusy), _)
+      | (Some(ffi::ErrorCode::DatabaseLocked), _)    => Some(Error::Busy),
+      (Some(ffi::ErrorCode::Unknown), _) if e.to_string().contains("syntax error") => Some(Error::SyntaxError),
+      _ => None,
+    };
+    let e = Error::RusqliteError(e);
+    match wrap { Some(wrap) => wrap(Box::new(e)), None => e }
+  }
+}
+
+#[cfg(feature = "mysql")]
+impl std::convert::From<mysql::Error> for Error {
+  fn from(e: mysql::Error) -> Self {
+    let wrap: Option<fn(Box<Error>) -> Error> = match &e {
+      mysql::Error::MySqlError(error) => match error.code {
+        1062 | 1586 => Some(Error::UniqueViolation),
+        1216 | 1217 | 1451 | 1452 => Some(Error::ForeignKeyViolation),
+        1048 | 1364 => Some(Error::NotNullViolation),
+        1205 | 1213 => Some(Error::Busy),
+        3024 => Some(Error::Timeout),
+        1064 => Some(Error::SyntaxError),
+        _ => None,
+      },
+      mysql::Error::IoError(_)
+      | mysql::Error::CodecError(_) => Some(Error::ConnectionLost),
+      _ => None,
+    };
+    let e = Error::MysqlError(e);
+    match wrap { Some(wrap) => wrap(Box::new(e)), None => e }
+  }
+}
+
+#[cfg(feature = "postgres")]
+impl std::convert::From<::postgres::Error> for Error {
+  fn from(e: ::postgres::Error) -> Self {
+    use ::postgres::error::SqlState;
+    let wrap: Option<fn(Box<Error>) -> Error> = match e.code() {
+      Some(code) if *code == SqlState::UNIQUE_VIOLATION => Some(Error::UniqueViolation),
+      Some(code) if *code == SqlState::FOREIGN_KEY_VIOLATION => Some(Error::ForeignKeyViolation),
+      Some(code) if *code == SqlState::NOT_NULL_VIOLATION => Some(Error::NotNullViolation),
+      Some(code) if *code == SqlState::T_R_DEADLOCK_DETECTED
+                 || *code == SqlState::T_R_SERIALIZATION_FAILURE
+                 || *code == SqlState::LOCK_NOT_AVAILABLE => Some(Error::Busy),
+      Some(code) if *code == SqlState::QUERY_CANCELED => Some(Error::Timeout),
+      Some(code) if *code == SqlState::SYNTAX_ERROR => Some(Error::SyntaxError),
+      Some(code) if *code == SqlState::ADMIN_SHUTDOWN
+                 || code.code().starts_with("08") => Some(Error::ConnectionLost),
+      None if e.is_closed() => Some(Error::ConnectionLost),
+      _ => None,
+    };
+    let e = Error::PostgresError(e);
+    match wrap { Some(wrap) => wrap(Box::new(e)), None => e }
+  }
+}
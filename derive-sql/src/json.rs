@@ -0,0 +1,56 @@
+//! Conversion of structured data to and from JSON columns, used by `DeriveSqlStatement` for fields nominated with
+//! `#[derive_sql(json)]`. Requires feature `json`.
+//!
+//! ```rust
+//! use derive_sql::traits::{self, Insert, SelectV2};
+//!
+//! #[derive(derive_sql::DeriveSqlStatement)]
+//! struct Artwork {
+//!   title: String,
+//!   #[derive_sql(json)]
+//!   artists: Vec<String>,
+//!   #[derive_sql(json)]
+//!   dimensions: Option<Vec<f64>>,
+//! }
+//!
+//! let mut conn = derive_sql::proxy::Mock::from_flavor(traits::Flavor::SQLite)
+//!   .with_rows("FROM `artwork`", vec![vec![
+//!     traits::Value::Text("Starry Night".to_string()), traits::Value::Text(r#"["Vincent van Gogh"]"#.to_string()), traits::Value::Null,
+//!   ]]);
+//! let db = SqlArtwork::default();
+//! db.insert(&mut conn, &Artwork { title: "Water Lilies".to_string(), artists: vec!["Claude Monet".to_string()], dimensions: Some(vec![200.0, 1275.0]) }).unwrap();
+//! assert!(matches!(conn.statements()[0].params(), [_, traits::Param::Json(a), traits::Param::Json(d)] if a.to_string().eq(r#"["Claude Monet"]"#) && d.to_string().eq("[200.0,1275.0]")));
+//! db.insert(&mut conn, &Artwork { title: "Sunflowers".to_string(), artists: vec!["Vincent van Gogh".to_string()], dimensions: None }).unwrap();
+//! assert!(matches!(conn.statements()[1].params(), [_, traits::Param::Json(_), traits::Param::Null]));
+//!
+//! let artworks: Vec<Artwork> = db.select(&mut conn).unwrap();
+//! assert!(artworks[0].artists == vec!["Vincent van Gogh".to_string()] && artworks[0].dimensions.is_none());
+//! ```
+use super::*;
+
+/// Serialize the value to a JSON parameter. Values serialized as JSON `null` - for example `None` for an `Option` -
+/// are bound as SQL `NULL`.
+pub fn to_param<T>(v: &T) -> Result<traits::Param>
+where T: serde::Serialize,
+{
+  match serde_json::to_value(v)? {
+    serde_json::Value::Null => Ok(traits::Param::Null),
+    v => Ok(traits::Param::Json(v)),
+  }
+}
+
+/// Deserialize the value from the JSON document retrieved from the database. `NULL` deserializes as JSON `null`,
+/// for example to `None` for an `Option`.
+pub fn try_from_value<T>(v: traits::Value) -> Result<T>
+where T: serde::de::DeserializeOwned,
+{
+  match v {
+    traits::Value::Null => Ok(serde_json::from_value(serde_json::Value::Null)?),
+#[cfg(feature = "mysql")]
+    traits::Value::MysqlValue(::mysql::Value::NULL) => Ok(serde_json::from_value(serde_json::Value::Null)?),
+    v => {
+      let s: String = traits::TryFromValue::try_from(v)?;
+      Ok(serde_json::from_str(s.as_str())?)
+    },
+  }
+}
@@ -353,8 +353,15 @@ pub use mysql;
 /// Re-export 'postgres' library used
 pub use postgres;
 
+/// Re-export `chrono` library used
+pub use chrono;
+
 pub mod traits;
+#[cfg(feature="json")]
+pub mod json;
 pub mod proxy;
+pub mod pool;
+pub mod migrate;
 pub mod structs; // pub use structs::{Field, filter, order};
 
 #[cfg(feature="compatibility_v0_10")]
@@ -402,7 +409,8 @@ pub use derive_sql_sqlite::DeriveSqlite;
 /// can be saved, queried, stored to/from a MySQL database. Uses `mysql`. Requires `--features mysql`.
 pub use derive_sql_mysql::DeriveMysql;
 
-pub use derive_sql_statement::DeriveSqlStatement;
+pub use derive_sql_statement::{DeriveSqlStatement, DeriveSqlEnum};
 
 mod error;
 pub use error::{Result, DeriveSqlResult, Error};
+pub(crate) use error::StatementContext;
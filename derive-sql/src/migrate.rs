@@ -0,0 +1,326 @@
+//! Schema migrations applied in version order and recorded - with their checksum - in table `_derive_sql_migrations`
+//!
+//! Each migration is applied in its own transaction together with the insertion of its version in the tracking
+//! table. Migrations already applied are skipped, and a migration modified after having been applied is refused.
+//! Note that MySQL commits DDL statements implicitly such that a failed MySQL migration may be partially applied.
+//!
+//! ```rust
+//! # #[cfg(feature = "sqlite")]
+//! # fn wrapper() -> derive_sql::Result<()> {
+//! use derive_sql::traits::Connection;
+//! use derive_sql::migrate::{Migration, Migrations};
+//!
+//! let mut conn = rusqlite::Connection::open_in_memory()?;
+//! let migrations = Migrations::default()
+//!   .with_migration(Migration::from_sql(1, "create artist", &["CREATE TABLE artist ( name TEXT NOT NULL )"])
+//!     .with_down_sql(&["DROP TABLE artist"]))
+//!   .with_migration(Migration::from_function(2, "add picasso", |conn| {
+//!       conn.execute_with_params("INSERT INTO artist (name) VALUES (?)", &"Pablo Picasso")?;
+//!       Ok(())
+//!     })
+//!     .with_down_sql(&["DELETE FROM artist"]));
+//!
+//! assert!(migrations.migrate(&mut conn)? == vec![1, 2]);
+//! assert!(migrations.migrate(&mut conn)?.is_empty());
+//! assert!(migrations.applied(&mut conn)?.iter().map(|m| m.version).collect::<Vec<i64>>() == vec![1, 2]);
+//!
+//! // Modifying a migration already applied is refused
+//! let modified = Migrations::default()
+//!   .with_migration(Migration::from_sql(1, "create artist", &["CREATE TABLE artist ( name TEXT NULL )"]))
+//!   .with_migration(Migration::from_sql(2, "add picasso", &[]));
+//! assert!(matches!(modified.migrate(&mut conn), Err(derive_sql::Error::MigrationModified(1))));
+//!
+//! assert!(migrations.revert_to(&mut conn, 0)? == vec![2, 1]);
+//! assert!(conn.query_drop("SELECT name FROM artist").is_err());
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+use traits::Connection;
+
+/// Name of the table recording the migrations applied
+pub const MIGRATIONS_TABLE: &str = "_derive_sql_migrations";
+
+type Function<C, R> = Box<dyn Fn(&mut traits::Transaction<'_, C, R>) -> Result<()>>;
+
+/// Step of a migration: SQL statements executed in order or a function
+enum Step<C, R>
+where C: Connection<R>,
+      R: traits::Row,
+{
+  Sql(Vec<String>),
+  Function(Function<C, R>),
+}
+
+impl<C, R> Step<C, R>
+where C: Connection<R>,
+      R: traits::Row,
+{
+  fn from_sql(sql: &[&str]) -> Step<C, R> {
+    Step::Sql(sql.iter().map(|s| s.to_string()).collect())
+  }
+
+  fn run(&self, tx: &mut traits::Transaction<'_, C, R>) -> Result<()> {
+    match self {
+      Step::Sql(statements) => statements.iter().try_for_each(|s| tx.query_drop(s)),
+      Step::Function(f)     => f(tx),
+    }
+  }
+}
+
+/// Migration identified by its version. The migration is made of an `up` step applying the change and an
+/// optional `down` step reverting it.
+pub struct Migration<C, R>
+where C: Connection<R>,
+      R: traits::Row,
+{
+  version: i64,
+  name: String,
+  checksum: String,
+  up: Step<C, R>,
+  down: Option<Step<C, R>>,
+}
+
+impl<C, R> Migration<C, R>
+where C: Connection<R>,
+      R: traits::Row,
+{
+  /// Migration executing the SQL statements in order. The checksum is computed from the statements
+  pub fn from_sql(version: i64, name: &str, sql: &[&str]) -> Migration<C, R> {
+    Migration { version, name: name.to_string(), checksum: checksum(sql.iter().copied()), up: Step::from_sql(sql), down: None, }
+  }
+
+  /// Migration calling the function with the transaction in which the migration is applied. The checksum is
+  /// computed from the name of the migration as the content of the function can not be verified
+  pub fn from_function<F>(version: i64, name: &str, f: F) -> Migration<C, R>
+  where F: Fn(&mut traits::Transaction<'_, C, R>) -> Result<()> + 'static,
+  {
+    Migration { version, name: name.to_string(), checksum: checksum([name]), up: Step::Function(Box::new(f)), down: None, }
+  }
+
+  /// Revert the migration by executing the SQL statements in order
+  pub fn with_down_sql(mut self, sql: &[&str]) -> Self {
+    self.down = Some(Step::from_sql(sql));
+    self
+  }
+
+  /// Revert the migration by calling the function
+  pub fn with_down_function<F>(mut self, f: F) -> Self
+  where F: Fn(&mut traits::Transaction<'_, C, R>) -> Result<()> + 'static,
+  {
+    self.down = Some(Step::Function(Box::new(f)));
+    self
+  }
+
+  /// Version of the migration
+  pub fn version(&self) -> i64 { self.version }
+
+  /// Name of the migration
+  pub fn name(&self) -> &str { self.name.as_str() }
+
+  /// Checksum recorded when the migration is applied
+  pub fn checksum(&self) -> &str { self.checksum.as_str() }
+}
+
+/// Migration recorded as applied in the tracking table
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedMigration {
+  pub version: i64,
+  pub name: String,
+  pub checksum: String,
+  /// Time - in UTC - at which the migration was applied
+  pub applied_at: chrono::naive::NaiveDateTime,
+}
+
+impl<R> traits::TryFromRefRow<R> for AppliedMigration
+where R: traits::Row,
+{
+  fn try_from(r: &R) -> Result<Self> {
+    Ok(AppliedMigration {
+      version:    r.get(0).ok_or(Error::RowItemNotFound(0))??,
+      name:       r.get(1).ok_or(Error::RowItemNotFound(1))??,
+      checksum:   r.get(2).ok_or(Error::RowItemNotFound(2))??,
+      applied_at: r.get(3).ok_or(Error::RowItemNotFound(3))??,
+    })
+  }
+}
+
+/// Ordered list of migrations
+pub struct Migrations<C, R>
+where C: Connection<R>,
+      R: traits::Row,
+{
+  migrations: Vec<Migration<C, R>>,
+}
+
+impl<C, R> std::default::Default for Migrations<C, R>
+where C: Connection<R>,
+      R: traits::Row,
+{
+  fn default() -> Self { Migrations { migrations: Vec::new() } }
+}
+
+impl<C, R> Migrations<C, R>
+where C: Connection<R>,
+      R: traits::Row,
+{
+  /// Register a migration. Migrations are applied in increasing version order irrespective of the registration order
+  pub fn with_migration(mut self, migration: Migration<C, R>) -> Self {
+    let i = self.migrations.partition_point(|m| m.version <= migration.version);
+    self.migrations.insert(i, migration);
+    self
+  }
+
+  /// Migrations recorded as applied, in increasing version order. The tracking table is created if it does not exist
+  pub fn applied(&self, conn: &mut C) -> Result<Vec<AppliedMigration>> {
+    let flavor = conn.flavor();
+    conn.query_drop(format!("CREATE TABLE IF NOT EXISTS {table} ( \
+      {version} {version_type}, {name} {name_type}, {checksum} {checksum_type}, {applied_at} {applied_at_type}, \
+      PRIMARY KEY ( {version} ) )",
+      table           = flavor.table(MIGRATIONS_TABLE)?,
+      version         = flavor.column("version")?,
+      version_type    = flavor.sql_type_nullable("i64", false)?,
+      name            = flavor.column("name")?,
+      name_type       = flavor.sql_type_nullable("String", false)?,
+      checksum        = flavor.column("checksum")?,
+      checksum_type   = flavor.sql_type_nullable("String", false)?,
+      applied_at      = flavor.column("applied_at")?,
+      applied_at_type = flavor.sql_type_nullable("NaiveDateTime", false)?,
+    ))?;
+    conn.query_try_as_object(format!("SELECT {version}, {name}, {checksum}, {applied_at} FROM {table} ORDER BY {version}",
+      table      = flavor.table(MIGRATIONS_TABLE)?,
+      version    = flavor.column("version")?,
+      name       = flavor.column("name")?,
+      checksum   = flavor.column("checksum")?,
+      applied_at = flavor.column("applied_at")?,
+    ))
+  }
+
+  /// Apply the pending migrations. Returns the versions applied
+  pub fn migrate(&self, conn: &mut C) -> Result<Vec<i64>> {
+    self.migrate_to(conn, i64::MAX)
+  }
+
+  /// Apply the pending migrations up to and including `version`. Returns the versions applied. A pending migration
+  /// with a version lower than the latest migration applied is refused.
+  pub fn migrate_to(&self, conn: &mut C, version: i64) -> Result<Vec<i64>> {
+    let applied = self.verified_applied(conn)?;
+    let latest = applied.iter().map(|m| m.version).max();
+    let pending = self.migrations.iter()
+      .filter(|m| m.version <= version && ! applied.iter().any(|a| a.version == m.version))
+      .collect::<Vec<&Migration<C, R>>>();
+    if let Some(m) = pending.iter().find(|m| latest.map(|latest| m.version < latest).unwrap_or(false)) {
+      return Err(Error::MigrationOutOfOrder(m.version));
+    }
+
+    let flavor = conn.flavor();
+    let stmt = format!("INSERT INTO {table} ( {version}, {name}, {checksum}, {applied_at} ) VALUES ( {v1}, {v2}, {v3}, {v4} )",
+      table      = flavor.table(MIGRATIONS_TABLE)?,
+      version    = flavor.column("version")?,
+      name       = flavor.column("name")?,
+      checksum   = flavor.column("checksum")?,
+      applied_at = flavor.column("applied_at")?,
+      v1 = flavor.value(0)?, v2 = flavor.value(1)?, v3 = flavor.value(2)?, v4 = flavor.value(3)?,
+    );
+    pending.into_iter()
+    .map(|m| {
+      let mut tx = conn.begin_transaction()?;
+      m.up.run(&mut tx)?;
+      tx.execute_with_params(stmt.as_str(), &(m.version, m.name.as_str(), m.checksum.as_str(), chrono::Utc::now().naive_utc()))?;
+      tx.commit()?;
+      Ok(m.version)
+    })
+    .collect()
+  }
+
+  /// Revert the migrations applied with a version greater than `version`, in decreasing version order. Returns the
+  /// versions reverted. Nothing is reverted when one of these migrations has no `down` step.
+  pub fn revert_to(&self, conn: &mut C, version: i64) -> Result<Vec<i64>> {
+    let applied = self.verified_applied(conn)?;
+    let reverted = applied.iter().rev()
+      .filter(|a| a.version > version)
+      .map(|a| {
+        let m = self.migrations.iter().find(|m| m.version == a.version).ok_or(Error::MigrationNotRegistered(a.version))?;
+        let down = m.down.as_ref().ok_or(Error::MigrationIrreversible(m.version))?;
+        Ok((m.version, down))
+      })
+      .collect::<Result<Vec<(i64, &Step<C, R>)>>>()?;
+
+    let flavor = conn.flavor();
+    let stmt = format!("DELETE FROM {table} WHERE {version} = {v1}",
+      table   = flavor.table(MIGRATIONS_TABLE)?,
+      version = flavor.column("version")?,
+      v1      = flavor.value(0)?,
+    );
+    reverted.into_iter()
+    .map(|(version, down)| {
+      let mut tx = conn.begin_transaction()?;
+      down.run(&mut tx)?;
+      tx.execute_with_params(stmt.as_str(), &version)?;
+      tx.commit()?;
+      Ok(version)
+    })
+    .collect()
+  }
+
+  /// Migrations applied, verified against the migrations registered
+  fn verified_applied(&self, conn: &mut C) -> Result<Vec<AppliedMigration>> {
+    if let Some(w) = self.migrations.windows(2).find(|w| w[0].version == w[1].version) {
+      return Err(Error::MigrationDuplicateVersion(w[0].version));
+    }
+    let applied = self.applied(conn)?;
+    for a in applied.iter() {
+      match self.migrations.iter().find(|m| m.version == a.version) {
+        None => return Err(Error::MigrationNotRegistered(a.version)),
+        Some(m) if m.checksum.ne(&a.checksum) => return Err(Error::MigrationModified(a.version)),
+        Some(_) => {},
+      }
+    }
+    Ok(applied)
+  }
+}
+
+/// FNV-1a hash of the items as an hexadecimal string. The hash is stable across platforms and Rust versions
+fn checksum<'a, I>(items: I) -> String
+where I: IntoIterator<Item = &'a str>,
+{
+  let hash = items.into_iter()
+    .flat_map(|s| s.as_bytes().iter().copied().chain(std::iter::once(0u8)))
+    .fold(0xcbf29ce484222325u64, |hash, b| (hash ^ b as u64).wrapping_mul(0x100000001b3));
+  format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_generates_postgresql_statements() -> Result<()> {
+    use traits::Value;
+
+    let migrations = Migrations::default()
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE artist ( name TEXT )"]).with_down_sql(&["DROP TABLE artist"]));
+
+    let mut conn = proxy::Mock::from_flavor(traits::Flavor::PostgreSQL);
+    assert!(migrations.migrate(&mut conn)? == vec![1]);
+    assert!(conn.sql() == vec![
+      "CREATE TABLE IF NOT EXISTS _derive_sql_migrations ( version BIGINT NOT NULL, name TEXT NOT NULL, checksum TEXT NOT NULL, applied_at TIMESTAMP NOT NULL, PRIMARY KEY ( version ) )",
+      "SELECT version, name, checksum, applied_at FROM _derive_sql_migrations ORDER BY version",
+      "BEGIN",
+      "CREATE TABLE artist ( name TEXT )",
+      "INSERT INTO _derive_sql_migrations ( version, name, checksum, applied_at ) VALUES ( $1, $2, $3, $4 )",
+      "COMMIT",
+    ]);
+    assert!(conn.statements()[4].params().len() == 4);
+
+    let checksum = migrations.migrations[0].checksum().to_string();
+    let mut conn = proxy::Mock::from_flavor(traits::Flavor::PostgreSQL)
+      .with_rows("FROM _derive_sql_migrations", vec![vec![
+        Value::Integer(1), Value::Text("create".to_string()), Value::Text(checksum), Value::Text("2026-01-01 00:00:00".to_string()),
+      ]]);
+    assert!(migrations.revert_to(&mut conn, 0)? == vec![1]);
+    assert!(conn.sql().ends_with(&["BEGIN", "DROP TABLE artist", "DELETE FROM _derive_sql_migrations WHERE version = $1", "COMMIT"]));
+    assert!(conn.statements()[4].params().len() == 1);
+    Ok(())
+  }
+}
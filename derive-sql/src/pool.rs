@@ -0,0 +1,317 @@
+//! Generic thread-safe pool of connections implementing `traits::Connection`
+//!
+//! ```rust
+//! # #[cfg(feature = "sqlite")]
+//! # fn wrapper() -> derive_sql::Result<()> {
+//! use derive_sql::traits::Connection;
+//!
+//! let path = std::env::temp_dir().join("derive_sql_pool_example.db");
+//! let pool = derive_sql::pool::Pool::from_factory(move || Ok(rusqlite::Connection::open(&path)?))
+//!   .with_max_size(4)
+//!   .with_idle_timeout(std::time::Duration::from_secs(60))
+//!   .with_health_check(|conn| conn.query_drop("SELECT 1"));
+//!
+//! let mut conn = pool.get()?;
+//! conn.query_drop("CREATE TABLE IF NOT EXISTS example_pool (id INTEGER)")?;
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+mod pooled_connection; pub use pooled_connection::PooledConnection;
+
+use std::sync::{Arc, Mutex, MutexGuard, Condvar};
+use std::time::{Duration, Instant};
+
+type Factory<C> = Arc<dyn Fn() -> Result<C> + Send + Sync>;
+type HealthCheck<C> = Arc<dyn Fn(&mut C) -> Result<()> + Send + Sync>;
+
+/// Pool of connections of type `C`. Connections are created on demand using the factory up to the maximum size
+/// of the pool and returned to the pool when the `PooledConnection` checked out is dropped. The pool can be
+/// cloned and shared between threads: clones share the same connections.
+pub struct Pool<C, R>
+where C: traits::Connection<R>,
+      R: traits::Row,
+{
+  factory: Factory<C>,
+  health_check: Option<HealthCheck<C>>,
+  max_size: usize,
+  idle_timeout: Option<Duration>,
+  checkout_timeout: Duration,
+  shared: Arc<Shared<C>>,
+  phantom_r: std::marker::PhantomData<fn() -> R>,
+}
+
+struct Shared<C> {
+  state: Mutex<State<C>>,
+  released: Condvar,
+}
+
+struct State<C> {
+  idle: Vec<Idle<C>>,
+  size: usize,
+}
+
+struct Idle<C> {
+  conn: C,
+  since: Instant,
+}
+
+impl<C, R> Pool<C, R>
+where C: traits::Connection<R>,
+      R: traits::Row,
+{
+  /// Create a pool using `factory` to open new connections. Default to a maximum of 10 connections, idle connections
+  /// closed after 10 minutes and a checkout timeout of 30 seconds.
+  pub fn from_factory<F>(factory: F) -> Pool<C, R>
+  where F: Fn() -> Result<C> + Send + Sync + 'static,
+  {
+    Pool {
+      factory: Arc::new(factory),
+      health_check: None,
+      max_size: 10,
+      idle_timeout: Some(Duration::from_secs(600)),
+      checkout_timeout: Duration::from_secs(30),
+      shared: Arc::new(Shared { state: Mutex::new(State { idle: Vec::new(), size: 0 }), released: Condvar::new() }),
+      phantom_r: std::marker::PhantomData,
+    }
+  }
+
+  /// Maximum number of connections opened by the pool
+  pub fn with_max_size(mut self, max_size: usize) -> Pool<C, R> {
+    self.max_size = max_size;
+    self
+  }
+
+  /// Duration after which connections that have not been checked out are closed
+  pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Pool<C, R> {
+    self.idle_timeout = Some(idle_timeout);
+    self
+  }
+
+  /// Keep idle connections open irrespective of the time they have not been used
+  pub fn without_idle_timeout(mut self) -> Pool<C, R> {
+    self.idle_timeout = None;
+    self
+  }
+
+  /// Maximum duration `get` waits for a connection to be released when all connections are checked out
+  pub fn with_checkout_timeout(mut self, checkout_timeout: Duration) -> Pool<C, R> {
+    self.checkout_timeout = checkout_timeout;
+    self
+  }
+
+  /// Check run on an idle connection before it is checked out. Connections failing the check are closed
+  /// and replaced.
+  pub fn with_health_check<F>(mut self, health_check: F) -> Pool<C, R>
+  where F: Fn(&mut C) -> Result<()> + Send + Sync + 'static,
+  {
+    self.health_check = Some(Arc::new(health_check));
+    self
+  }
+
+  /// Number of connections currently opened by the pool, ie idle and checked out
+  pub fn size(&self) -> usize { self.lock().size }
+
+  /// Number of idle connections available for checkout
+  pub fn idle_size(&self) -> usize { self.lock().idle.len() }
+
+  /// Check out a connection. Re-use an idle connection when available, open a new connection if the pool
+  /// has not reached its maximum size, or wait for a connection to be released otherwise.
+  pub fn get(&self) -> Result<PooledConnection<C, R>> {
+    let deadline = Instant::now() + self.checkout_timeout;
+    let mut state = self.lock();
+    loop {
+      self.close_expired(&mut state);
+
+      if let Some(Idle { mut conn, .. }) = state.idle.pop() {
+        drop(state);
+        match self.health_check.as_ref().map(|check| check(&mut conn)).transpose() {
+          Ok(_) => return Ok(PooledConnection::from_pool_connection(self.clone(), conn)),
+          Err(e) => {
+            log::warn!("Connection failed health check and is closed: {e}");
+            drop(conn);
+            state = self.lock();
+            state.size -= 1;
+          },
+        }
+
+      } else if state.size < self.max_size {
+        state.size += 1;
+        drop(state);
+        return match (self.factory)() {
+          Ok(conn) => Ok(PooledConnection::from_pool_connection(self.clone(), conn)),
+          Err(e) => {
+            self.lock().size -= 1;
+            self.shared.released.notify_one();
+            Err(e)
+          },
+        };
+
+      } else {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        if timeout.is_zero() { return Err(Error::PoolCheckoutTimeout); }
+        state = self.shared.released.wait_timeout(state, timeout)
+          .unwrap_or_else(|e| e.into_inner()).0;
+      }
+    }
+  }
+
+  /// Return a connection to the pool
+  fn release(&self, conn: C) {
+    self.lock().idle.push(Idle { conn, since: Instant::now() });
+    self.shared.released.notify_one();
+  }
+
+  /// Account for a checked out connection closed rather than returned to the pool
+  fn discard(&self) {
+    self.lock().size -= 1;
+    self.shared.released.notify_one();
+  }
+
+  fn close_expired(&self, state: &mut State<C>) {
+    if let Some(idle_timeout) = self.idle_timeout {
+      let n = state.idle.len();
+      state.idle.retain(|idle| idle.since.elapsed() < idle_timeout);
+      state.size -= n - state.idle.len();
+    }
+  }
+
+  // Pool state remains consistent if a thread panics while holding the lock
+  fn lock(&self) -> MutexGuard<'_, State<C>> {
+    self.shared.state.lock().unwrap_or_else(|e| e.into_inner())
+  }
+}
+
+impl<C, R> Clone for Pool<C, R>
+where C: traits::Connection<R>,
+      R: traits::Row,
+{
+  fn clone(&self) -> Self {
+    Pool {
+      factory: self.factory.clone(),
+      health_check: self.health_check.clone(),
+      max_size: self.max_size,
+      idle_timeout: self.idle_timeout,
+      checkout_timeout: self.checkout_timeout,
+      shared: self.shared.clone(),
+      phantom_r: std::marker::PhantomData,
+    }
+  }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+  use super::*;
+  use traits::Connection;
+
+  /// Database file removed when dropped
+  struct TempDb(std::path::PathBuf);
+
+  impl Drop for TempDb {
+    fn drop(&mut self) { let _ = std::fs::remove_file(&self.0); }
+  }
+
+  fn sqlite_pool(name: &str) -> Result<(Pool<rusqlite::Connection, proxy::sqlite::Row>, TempDb)> {
+    let path = std::env::temp_dir().join(format!("derive_sql_pool_{name}_{}.db", std::process::id()));
+    if path.exists() { std::fs::remove_file(&path).map_err(|e| Error::Misc(e.to_string()))?; }
+    let db = TempDb(path.clone());
+    Ok((Pool::from_factory(move || Ok(rusqlite::Connection::open(&path)?)), db))
+  }
+
+  #[test]
+  fn it_shares_connections_between_threads() -> Result<()> {
+    let (pool, _db) = sqlite_pool("threads")?;
+    let pool = pool.with_max_size(3);
+    pool.get()?.query_drop("CREATE TABLE pool_threads ( id INTEGER )")?;
+
+    let handles = (0..8).map(|i| {
+      let pool = pool.clone();
+      std::thread::spawn(move || -> Result<()> {
+        let mut conn = pool.get()?;
+        conn.execute_with_params("INSERT INTO pool_threads (id) VALUES (?)", &(i as i64))?;
+        Ok(())
+      })
+    })
+    .collect::<Vec<_>>();
+    for handle in handles { handle.join().map_err(|_| Error::Misc("Thread panicked".to_string()))??; }
+
+    assert!(pool.size() <= 3);
+    let count: Option<i64> = pool.get()?.query_first_try_as_object("SELECT COUNT(*) FROM pool_threads")?;
+    assert!(count == Some(8));
+    Ok(())
+  }
+
+  #[test]
+  fn it_times_out_when_all_connections_are_checked_out() -> Result<()> {
+    let (pool, _db) = sqlite_pool("timeout")?;
+    let pool = pool.with_max_size(1).with_checkout_timeout(Duration::from_millis(10));
+    let conn = pool.get()?;
+    assert!(matches!(pool.get(), Err(Error::PoolCheckoutTimeout)));
+    drop(conn);
+    assert!(pool.get().is_ok());
+    assert!(pool.size() == 1);
+    Ok(())
+  }
+
+  #[test]
+  fn it_closes_idle_connections() -> Result<()> {
+    let (pool, _db) = sqlite_pool("idle")?;
+    let pool = pool.with_idle_timeout(Duration::from_millis(10));
+    drop(pool.get()?);
+    assert!(pool.size() == 1 && pool.idle_size() == 1);
+    std::thread::sleep(Duration::from_millis(20));
+    drop(pool.get()?);
+    assert!(pool.size() == 1 && pool.idle_size() == 1);
+    Ok(())
+  }
+
+  #[test]
+  fn it_replaces_connections_failing_health_check() -> Result<()> {
+    let checks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let (pool, _db) = sqlite_pool("health")?;
+    let pool = {
+      let checks = checks.clone();
+      pool.with_health_check(move |_conn| {
+        match checks.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+          0 => Err(Error::Misc("Connection lost".to_string())),
+          _ => Ok(()),
+        }
+      })
+    };
+    drop(pool.get()?);
+    drop(pool.get()?); // Idle connection fails the check and is replaced
+    assert!(pool.size() == 1);
+    drop(pool.get()?); // Idle connection passes the check
+    assert!(checks.load(std::sync::atomic::Ordering::SeqCst) == 2);
+    Ok(())
+  }
+
+  #[test]
+  fn it_runs_statements_through_pooled_connection() -> Result<()> {
+    let (pool, _db) = sqlite_pool("statements")?;
+    proxy::proxy_test::run_connection(&mut pool.get()?)?;
+    proxy::proxy_test::run_transaction(&mut pool.get()?)?;
+    proxy::proxy_test::run_query_iter(&mut pool.get()?)?;
+    Ok(())
+  }
+
+  #[test]
+  fn it_rolls_back_transaction_left_open_on_release() -> Result<()> {
+    let (pool, _db) = sqlite_pool("release")?;
+    let pool = pool.with_max_size(1);
+    pool.get()?.query_drop("CREATE TABLE pool_release ( id INTEGER )")?;
+    {
+      let mut conn = pool.get()?;
+      conn.begin()?;
+      conn.execute_with_params("INSERT INTO pool_release (id) VALUES (?)", &1i64)?;
+      conn.execute_with_params_iterator("INSERT INTO pool_release (id) VALUES (?)", [2i64, 3i64].iter())?;
+      assert!(! conn.inner_mut().is_autocommit());
+    }
+    let mut conn = pool.get()?;
+    assert!(conn.inner_mut().is_autocommit());
+    let count: Option<i64> = conn.query_first_try_as_object("SELECT COUNT(*) FROM pool_release")?;
+    assert!(count == Some(0));
+    Ok(())
+  }
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+/// Name of the savepoint used by `execute_with_params_iterator` within a transaction
+const SAVEPOINT: &str = "derive_sql_execute";
+
+/// Connection checked out from a `Pool`. The connection is returned to the pool when dropped. A transaction
+/// started with `begin` - or `begin_transaction` - and left open is rolled back beforehand.
+pub struct PooledConnection<C, R>
+where C: traits::Connection<R>,
+      R: traits::Row,
+{
+  pool: Pool<C, R>,
+  conn: Option<C>,
+  in_transaction: bool,
+}
+
+impl<C, R> PooledConnection<C, R>
+where C: traits::Connection<R>,
+      R: traits::Row,
+{
+  pub(super) fn from_pool_connection(pool: Pool<C, R>, conn: C) -> PooledConnection<C, R> {
+    PooledConnection { pool, conn: Some(conn), in_transaction: false, }
+  }
+
+  /// Underlying connection. Transactions started directly on the underlying connection are not rolled back
+  /// when the connection is returned to the pool.
+  pub fn inner_mut(&mut self) -> &mut C {
+    self.conn.as_mut().expect("Connection is only taken when dropped")
+  }
+
+  fn inner(&self) -> &C {
+    self.conn.as_ref().expect("Connection is only taken when dropped")
+  }
+}
+
+impl<C, R> Drop for PooledConnection<C, R>
+where C: traits::Connection<R>,
+      R: traits::Row,
+{
+  fn drop(&mut self) {
+    if let Some(mut conn) = self.conn.take() {
+      if self.in_transaction {
+        if let Err(e) = conn.rollback() {
+          log::warn!("Rollback of transaction on release failed, connection is closed: {e}");
+          self.pool.discard();
+          return;
+        }
+      }
+      self.pool.release(conn);
+    }
+  }
+}
+
+impl<C, R> traits::Connection<R> for PooledConnection<C, R>
+where C: traits::Connection<R>,
+      R: traits::Row,
+{
+  fn flavor(&self) -> traits::Flavor { self.inner().flavor() }
+
+  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<traits::ExecuteResult>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
+  {
+    self.inner_mut().execute_with_params(query, params)
+  }
+
+  /// Execute the statement for each item. Within a transaction started with `begin`, the statements run in a savepoint
+  /// rather than in their own transaction.
+  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<traits::ExecuteResult>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params + 'a,
+        I: core::iter::IntoIterator<Item = &'a P>
+  {
+    if ! self.in_transaction { return self.inner_mut().execute_with_params_iterator(query, params_iter); }
+
+    self.inner_mut().savepoint(SAVEPOINT)?;
+    let r = params_iter.into_iter()
+      .try_fold(traits::ExecuteResult::default(), |r, params| Ok(r.and(self.inner_mut().execute_with_params(query.as_ref(), params)?)));
+    if r.is_err() { self.inner_mut().rollback_to_savepoint(SAVEPOINT)?; }
+    self.inner_mut().release_savepoint(SAVEPOINT)?;
+    r
+  }
+
+  fn query<S>(&mut self, query: S) -> Result<Vec<R>>
+  where S: std::convert::AsRef<str>,
+  {
+    self.inner_mut().query(query)
+  }
+
+  fn query_with_params<S, P>(&mut self, query: S, params: &P) -> Result<Vec<R>>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
+  {
+    self.inner_mut().query_with_params(query, params)
+  }
+
+  fn query_with_params_iter<S, P, F, U>(&mut self, query: S, params: &P, f: F) -> Result<U>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
+        F: FnOnce(traits::QueryIterator<'_, R>) -> Result<U>,
+  {
+    self.inner_mut().query_with_params_iter(query, params, f)
+  }
+
+  fn begin(&mut self) -> Result<()> {
+    self.inner_mut().begin()?;
+    self.in_transaction = true;
+    Ok(())
+  }
+
+  fn commit(&mut self) -> Result<()> {
+    self.inner_mut().commit()?;
+    self.in_transaction = false;
+    Ok(())
+  }
+
+  fn rollback(&mut self) -> Result<()> {
+    self.inner_mut().rollback()?;
+    self.in_transaction = false;
+    Ok(())
+  }
+
+  fn savepoint(&mut self, name: &str) -> Result<()> { self.inner_mut().savepoint(name) }
+
+  fn release_savepoint(&mut self, name: &str) -> Result<()> { self.inner_mut().release_savepoint(name) }
+
+  fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> { self.inner_mut().rollback_to_savepoint(name) }
+}
@@ -2,6 +2,7 @@
 use super::*;
 
 mod log; pub use log::Log;
+mod mock; pub use mock::{Mock, MockRow, Statement as MockStatement};
 
 #[cfg(feature="sqlite")]
 pub mod sqlite;
@@ -12,6 +13,11 @@ pub mod mysql;
 #[cfg(feature="postgres")]
 pub mod postgres;
 
+#[cfg(any(feature="sqlite", feature="mysql", feature="postgres"))]
+mod any;
+#[cfg(any(feature="sqlite", feature="mysql", feature="postgres"))]
+pub use any::{AnyConnection, AnyRow};
+
 #[cfg(test)]
 pub mod proxy_test {
   use super::*;
@@ -64,4 +70,530 @@ pub mod proxy_test {
 
     Ok(())
   }
+
+  pub fn run_transaction<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::Connection;
+
+    s.query_drop("DROP TABLE IF EXISTS run_transaction")?;
+    s.query_drop("CREATE TABLE run_transaction ( id INTEGER )")?;
+    let count = |s: &mut S| -> Result<i64> {
+      s.query_first_try_as_object::<_, i64>("SELECT COUNT(*) FROM run_transaction")?
+      .ok_or(Error::QueryReturnNoResult)
+    };
+
+    // Committed transaction
+    let mut tx = s.begin_transaction()?;
+    tx.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &1i64)?;
+    tx.execute_with_params_iterator("INSERT INTO run_transaction (id) VALUES (?)", [2i64, 3i64].iter())?;
+    tx.commit()?;
+    assert!(count(s)? == 3);
+
+    // Transaction rolled back explicitely and on drop
+    let mut tx = s.begin_transaction()?;
+    tx.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &4i64)?;
+    tx.execute_with_params_iterator("INSERT INTO run_transaction (id) VALUES (?)", [5i64, 6i64].iter())?;
+    tx.rollback()?;
+    assert!(count(s)? == 3);
+
+    // Failed statements are rolled back leaving the transaction usable
+    let mut tx = s.begin_transaction()?;
+    assert!(tx.execute_with_params_iterator("INSERT INTO run_transaction_unknown (id) VALUES (?)", [4i64].iter()).is_err());
+    tx.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &4i64)?;
+    tx.rollback()?;
+    assert!(count(s)? == 3);
+    {
+      let mut tx = s.begin_transaction()?;
+      tx.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &4i64)?;
+    }
+    assert!(count(s)? == 3);
+
+    // Nested transactions using savepoints
+    let mut tx = s.begin_transaction()?;
+    tx.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &4i64)?;
+    {
+      let mut sp = tx.begin_transaction()?;
+      sp.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &5i64)?;
+      sp.commit()?;
+    }
+    {
+      let mut sp = tx.begin_transaction()?;
+      sp.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &6i64)?;
+      {
+        let mut sp = sp.begin_transaction()?;
+        sp.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &7i64)?;
+        sp.commit()?;
+      }
+    }
+    assert!(tx.begin().is_err());
+    tx.commit()?;
+    assert!(count(s)? == 5);
+
+    // Transaction with low level statements
+    s.begin()?;
+    s.execute_with_params("INSERT INTO run_transaction (id) VALUES (?)", &8i64)?;
+    s.rollback()?;
+    assert!(count(s)? == 5);
+
+    s.query_drop("DROP TABLE run_transaction")?;
+    Ok(())
+  }
+
+  pub fn run_filter_with_params<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::{SelectV2, Update, Delete};
+    use structs::Field;
+
+    struct Statement {}
+    impl traits::SelectFlavoredStatement for Statement {
+      fn select_stmt<C, R>(&self, conn: &C) -> Result<String>
+      where C: traits::Connection<R>, R: traits::Row,
+      {
+        Ok(format!("SELECT {name} FROM run_filter_with_params", name = conn.flavor().column("name")?))
+      }
+    }
+    impl traits::UpdateFlavoredStatement for Statement {
+      fn update_stmt<C, R>(&self, conn: &C) -> Result<String>
+      where C: traits::Connection<R>, R: traits::Row,
+      {
+        Ok(format!("UPDATE run_filter_with_params SET {name} = {value}", name = conn.flavor().column("name")?, value = conn.flavor().value(0)?))
+      }
+    }
+    impl traits::DeleteStatement for Statement {
+      fn delete_stmt(&self) -> Result<String> { Ok("DELETE FROM run_filter_with_params".to_string()) }
+    }
+
+    s.query_drop("DROP TABLE IF EXISTS run_filter_with_params")?;
+    s.query_drop("CREATE TABLE run_filter_with_params ( id INTEGER, name TEXT )")?;
+    s.execute_with_params_iterator("INSERT INTO run_filter_with_params (id, name) VALUES (?, ?)",
+      [(1i64, "O'Brien".to_string()), (2i64, "Jane".to_string())].iter()
+    )?;
+
+    // Values containing quotes are matched and can not alter the statement
+    let r: Vec<String> = Statement {}.select_with_filter(s, &Field::from("name").eq("O'Brien"))?;
+    assert!(r.len() == 1 && r[0].eq("O'Brien"));
+    let r: Vec<String> = Statement {}.select_with_filter(s, &Field::from("name").eq("x' OR '1' = '1"))?;
+    assert!(r.is_empty());
+
+    // Filter values are bound after the object values
+    Statement {}.update_with_filter(s, &Field::from("name").eq("O'Brien"), &"O'Neil".to_string())?;
+    let r: Vec<String> = Statement {}.select_with_filter_order(s, 
+      &structs::filter::Or::from((Field::from("name").eq("O'Neil"), Field::from("name").eq("Jane"))),
+      &Field::from("name").ascending())?;
+    assert!(r.len() == 2 && r[0].eq("Jane") && r[1].eq("O'Neil"));
+
+    Statement {}.delete_with_filter(s, &structs::filter::And::from((Field::from("id").eq(1u32), Field::from("name").eq("O'Neil"))))?;
+    let r: Vec<String> = Statement {}.select(s)?;
+    assert!(r.len() == 1 && r[0].eq("Jane"));
+
+    s.query_drop("DROP TABLE run_filter_with_params")?;
+    Ok(())
+  }
+
+  pub fn run_query_iter<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::SelectV2;
+    use structs::Field;
+
+    struct Statement {}
+    impl traits::SelectStatement for Statement {
+      fn select_stmt(&self) -> Result<String> { Ok("SELECT id FROM run_query_iter".to_string()) }
+    }
+
+    s.query_drop("DROP TABLE IF EXISTS run_query_iter")?;
+    s.query_drop("CREATE TABLE run_query_iter ( id INTEGER )")?;
+    s.execute_with_params_iterator("INSERT INTO run_query_iter (id) VALUES (?)", [1i64, 2i64, 3i64, 4i64].iter())?;
+
+    let count = s.query_iter("SELECT id FROM run_query_iter", |rows| Ok(rows.count()))?;
+    assert!(count == 4);
+
+    let first_two: Vec<i64> = s.query_iter_try_as_object("SELECT id FROM run_query_iter ORDER BY id", |ids| ids.take(2).collect())?;
+    assert!(first_two.len() == 2 && first_two[0] == 1 && first_two[1] == 2);
+
+    let sum: i64 = Statement {}.select_iter(s, |ids: traits::QueryIterator<i64>| ids.sum())?;
+    assert!(sum == 10);
+
+    let sum: i64 = Statement {}.select_with_filter_iter(s, &Field::from("id").gt(2u32), |ids: traits::QueryIterator<i64>| ids.sum())?;
+    assert!(sum == 7);
+
+    // Errors returned by the closure are returned to the caller
+    let r: Result<()> = s.query_iter("SELECT id FROM run_query_iter", |_| Err(Error::QueryReturnNoResult));
+    assert!(matches!(r, Err(Error::QueryReturnNoResult)));
+
+    // The connection remains usable after the iterator has been dropped before completion
+    s.query_iter("SELECT id FROM run_query_iter", |mut rows| { rows.next(); Ok(()) })?;
+    s.query_drop("DROP TABLE run_query_iter")?;
+    Ok(())
+  }
+
+  pub fn run_query_with_params<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    s.query_drop("DROP TABLE IF EXISTS run_query_with_params")?;
+    s.query_drop("CREATE TABLE run_query_with_params ( id INTEGER, name TEXT )")?;
+    s.execute_with_params_iterator("INSERT INTO run_query_with_params (id, name) VALUES (?, ?)",
+      [(1i64, "O'Brien".to_string()), (2i64, "Jane".to_string()), (3i64, "Jane".to_string())].iter()
+    )?;
+
+    let rows = s.query_with_params("SELECT id FROM run_query_with_params WHERE name = ?", &"Jane")?;
+    assert!(rows.len() == 2);
+
+    let ids: Vec<i64> = s.query_with_params_try_as_object("SELECT id FROM run_query_with_params WHERE name = ? AND id > ?", &("Jane", 2i64))?;
+    assert!(ids.len() == 1 && ids[0] == 3);
+
+    let name: String = s.query_first_with_params("SELECT name FROM run_query_with_params WHERE id = ?", &1i64)?
+    .map(|r| r.get::<String>(0).ok_or(Error::ResultConversionFail("String".to_string()))?)
+    .ok_or(Error::QueryReturnNoResult)??;
+    assert!(name.eq("O'Brien"));
+
+    let name: Option<String> = s.query_first_with_params_try_as_object("SELECT name FROM run_query_with_params WHERE id = ?", &2i64)?;
+    assert!(name.is_some_and(|name| name.eq("Jane")));
+    let name: Option<String> = s.query_first_with_params_try_as_object("SELECT name FROM run_query_with_params WHERE id = ?", &4i64)?;
+    assert!(name.is_none());
+
+    s.query_drop("DROP TABLE run_query_with_params")?;
+    Ok(())
+  }
+
+  pub fn run_execute_result<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::{Update, Delete};
+    use structs::Field;
+
+    struct Statement {}
+    impl traits::UpdateStatement for Statement {
+      fn update_stmt(&self) -> Result<String> { Ok("UPDATE run_execute_result SET name = ?".to_string()) }
+    }
+    impl traits::DeleteStatement for Statement {
+      fn delete_stmt(&self) -> Result<String> { Ok("DELETE FROM run_execute_result".to_string()) }
+    }
+
+    for table in ["run_execute_result", "run_execute_result_other"] {
+      s.query_drop(format!("DROP TABLE IF EXISTS {table}"))?;
+      match s.flavor() {
+        traits::Flavor::SQLite     => s.query_drop(format!("CREATE TABLE {table} ( id INTEGER PRIMARY KEY, name TEXT )"))?,
+        traits::Flavor::MySQL      => s.query_drop(format!("CREATE TABLE {table} ( id INTEGER PRIMARY KEY AUTO_INCREMENT, name TEXT )"))?,
+        traits::Flavor::PostgreSQL => s.query_drop(format!("CREATE TABLE {table} ( id SERIAL PRIMARY KEY, name TEXT )"))?,
+      }
+    }
+
+    let r = s.execute_with_params("INSERT INTO run_execute_result (name) VALUES (?)", &"Jane")?;
+    assert!(r.rows_affected() == 1);
+    assert!(r.last_insert_id() == Some(1));
+    // Row inserted in another table with the same identifier
+    let r = s.execute_with_params("INSERT INTO run_execute_result_other (name) VALUES (?)", &"Jane")?;
+    assert!(r.last_insert_id() == Some(1));
+    s.query_drop("DROP TABLE run_execute_result_other")?;
+
+    let r = s.execute_with_params_iterator("INSERT INTO run_execute_result (name) VALUES (?)", ["Jane", "John", "Jack"].iter())?;
+    assert!(r.rows_affected() == 3);
+    assert!(r.last_insert_id() == Some(4));
+
+    let r = Statement {}.update_with_filter(s, &Field::from("name").eq("Jane"), &"Janet".to_string())?;
+    assert!(r.rows_affected() == 2);
+    assert!(r.last_insert_id().is_none());
+    let r = Statement {}.update_with_filter(s, &Field::from("name").eq("Jane"), &"Janet".to_string())?;
+    assert!(r.rows_affected() == 0);
+
+    let r = Statement {}.delete_with_filter(s, &Field::from("name").eq("Janet"))?;
+    assert!(r.rows_affected() == 2);
+    let r = Statement {}.delete(s)?;
+    assert!(r.rows_affected() == 2);
+
+    s.query_drop("DROP TABLE run_execute_result")?;
+    Ok(())
+  }
+  pub fn run_many_params<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    let columns = (0..20).map(|i| format!("c{i}")).collect::<Vec<String>>();
+    let placeholders = (0..20).map(|_| "?").collect::<Vec<&str>>().join(", ");
+    s.query_drop("DROP TABLE IF EXISTS run_many_params")?;
+    s.query_drop(format!("CREATE TABLE run_many_params ( {} )",
+      columns.iter().map(|c| format!("{c} INTEGER")).collect::<Vec<String>>().join(", ")).as_str())?;
+    let insert = format!("INSERT INTO run_many_params ({}) VALUES ({placeholders})", columns.join(", "));
+
+    // Tuple
+    s.execute_with_params(insert.as_str(),
+      &(0i64, 1i64, 2i64, 3i64, 4i64, 5i64, 6i64, 7i64, 8i64, 9i64, 10i64, 11i64, 12i64, 13i64, 14i64, 15i64, 16i64, 17i64, 18i64, 19i64))?;
+    // Array and slice
+    let values: [i64; 20] = std::array::from_fn(|i| i as i64 + 100);
+    s.execute_with_params(insert.as_str(), &values)?;
+    s.execute_with_params(insert.as_str(), &&values[..])?;
+    // Vector of parameters
+    let params = traits::Params::as_vec_params(&values)?;
+    s.execute_with_params(insert.as_str(), &params)?;
+
+    let sums: Vec<i64> = s.query_with_params_try_as_object(
+      format!("SELECT {} FROM run_many_params WHERE c19 = ?", columns.join(" + ")).as_str(), &119i64)?;
+    assert!(sums.len() == 3 && sums.iter().all(|s| *s == (100..120).sum::<i64>()));
+
+    let count: Option<i64> = s.query_first_with_params_try_as_object(
+      format!("SELECT COUNT(*) FROM run_many_params WHERE {}",
+        columns.iter().map(|c| format!("{c} = ?")).collect::<Vec<String>>().join(" AND ")).as_str(), &params)?;
+    assert!(count == Some(3));
+
+    s.query_drop("DROP TABLE run_many_params")?;
+    Ok(())
+  }
+  pub fn run_returning<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::{InsertReturning, UpdateReturning, DeleteReturning};
+    use structs::Field;
+
+    struct Statement {}
+    impl traits::InsertStatement for Statement {
+      fn insert_stmt(&self) -> Result<String> { Ok("INSERT INTO run_returning (name) VALUES (?)".to_string()) }
+    }
+    impl traits::UpdateStatement for Statement {
+      fn update_stmt(&self) -> Result<String> { Ok("UPDATE run_returning SET name = ?".to_string()) }
+    }
+    impl traits::DeleteStatement for Statement {
+      fn delete_stmt(&self) -> Result<String> { Ok("DELETE FROM run_returning".to_string()) }
+    }
+    impl traits::ReturningStatement for Statement {
+      fn returning_stmt(&self) -> Result<String> { Ok("RETURNING name".to_string()) }
+    }
+
+    s.query_drop("DROP TABLE IF EXISTS run_returning")?;
+    s.query_drop("CREATE TABLE run_returning ( name TEXT )")?;
+
+    if let traits::Flavor::MySQL = s.flavor() {
+      let r: Result<String> = Statement {}.insert_returning(s, &"Jane".to_string());
+      assert!(matches!(r.as_ref().map_err(Error::inner), Err(Error::ReturningNotSupported(_))));
+      s.query_drop("DROP TABLE run_returning")?;
+      return Ok(());
+    }
+
+    let name: String = Statement {}.insert_returning(s, &"Jane".to_string())?;
+    assert!(name.eq("Jane"));
+    Statement {}.insert_returning(s, &"John".to_string())?;
+    Statement {}.insert_returning(s, &"Jane".to_string())?;
+
+    let names: Vec<String> = Statement {}.update_with_filter_returning(s, &Field::from("name").eq("Jane"), &"Janet".to_string())?;
+    assert!(names.len() == 2 && names.iter().all(|n| n.eq("Janet")));
+
+    let names: Vec<String> = Statement {}.delete_with_filter_returning(s, &Field::from("name").eq("John"))?;
+    assert!(names.len() == 1 && names[0].eq("John"));
+
+    let names: Vec<String> = Statement {}.update_returning(s, &"Jack".to_string())?;
+    assert!(names.len() == 2 && names.iter().all(|n| n.eq("Jack")));
+
+    let names: Vec<String> = Statement {}.delete_returning(s)?;
+    assert!(names.len() == 2);
+
+    s.query_drop("DROP TABLE run_returning")?;
+    Ok(())
+  }
+  pub fn run_upsert<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::Upsert;
+
+    struct Statement {}
+    impl traits::UpsertFlavoredStatement for Statement {
+      fn upsert_stmt<C, R>(&self, conn: &C) -> Result<String>
+      where C: traits::Connection<R>, R: traits::Row,
+      {
+        let flavor = conn.flavor();
+        Ok(format!("INSERT INTO run_upsert (id, name) VALUES ({}, {}) {}", flavor.value(0)?, flavor.value(1)?,
+          flavor.on_conflict_update(&["id".to_string()], &["name".to_string()])?))
+      }
+      fn insert_or_ignore_stmt<C, R>(&self, conn: &C) -> Result<String>
+      where C: traits::Connection<R>, R: traits::Row,
+      {
+        let flavor = conn.flavor();
+        Ok(format!("INSERT INTO run_upsert (id, name) VALUES ({}, {}) {}", flavor.value(0)?, flavor.value(1)?,
+          flavor.on_conflict_ignore(&["id".to_string()])?))
+      }
+    }
+
+    s.query_drop("DROP TABLE IF EXISTS run_upsert")?;
+    s.query_drop("CREATE TABLE run_upsert ( id INTEGER PRIMARY KEY, name TEXT )")?;
+
+    Statement {}.upsert(s, &(1i64, "Jane"))?;
+    Statement {}.upsert(s, &(2i64, "John"))?;
+    // Upsert updating an existing row does not report the identifier of a previous insert
+    let r = Statement {}.upsert(s, &(1i64, "Janet"))?;
+    assert!(r.last_insert_id().is_none());
+    let names: Vec<String> = s.query_try_as_object("SELECT name FROM run_upsert ORDER BY id")?;
+    assert!(names.len() == 2 && names[0].eq("Janet") && names[1].eq("John"));
+
+    let r = Statement {}.insert_or_ignore(s, &(2i64, "Jack"))?;
+    assert!(r.rows_affected() == 0);
+    Statement {}.insert_or_ignore(s, &(3i64, "Jack"))?;
+    let names: Vec<String> = s.query_try_as_object("SELECT name FROM run_upsert ORDER BY id")?;
+    assert!(names.len() == 3 && names[1].eq("John") && names[2].eq("Jack"));
+
+    s.query_drop("DROP TABLE run_upsert")?;
+    Ok(())
+  }
+  pub fn run_select_by_keys<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::SelectByKeys;
+
+    struct Statement {}
+    impl traits::SelectStatement for Statement {
+      fn select_stmt(&self) -> Result<String> { Ok("SELECT name FROM run_select_by_keys".to_string()) }
+    }
+
+    s.query_drop("DROP TABLE IF EXISTS run_select_by_keys")?;
+    s.query_drop("CREATE TABLE run_select_by_keys ( id INTEGER PRIMARY KEY, name TEXT )")?;
+    s.execute_with_params_iterator("INSERT INTO run_select_by_keys (id, name) VALUES (1, 'Jane'), (2, 'John'), (3, 'Jack')", [&()])?;
+
+    let mut names: Vec<String> = Statement {}.select_by_keys(s, "id", &[3i64, 1i64, 4i64])?;
+    names.sort();
+    assert!(names.len() == 2 && names[0].eq("Jack") && names[1].eq("Jane"));
+    let names: Vec<String> = Statement {}.select_by_keys::<i64>(s, "id", &[])?;
+    assert!(names.is_empty());
+    // Keys exceeding the number of parameters of a statement
+    let keys = (0..s.flavor().max_params() as i64 + 10).collect::<Vec<i64>>();
+    let names: Vec<String> = Statement {}.select_by_keys(s, "id", &keys)?;
+    assert!(names.len() == 3);
+
+    s.query_drop("DROP TABLE run_select_by_keys")?;
+    Ok(())
+  }
+
+  pub fn run_migrate<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    use traits::Connection;
+    use migrate::{Migration, Migrations};
+
+    s.query_drop("DROP TABLE IF EXISTS run_migrate")?;
+    s.query_drop(format!("DROP TABLE IF EXISTS {}", migrate::MIGRATIONS_TABLE))?;
+    let count = |s: &mut S| -> Result<i64> {
+      s.query_first_try_as_object::<_, i64>("SELECT COUNT(*) FROM run_migrate")?
+      .ok_or(Error::QueryReturnNoResult)
+    };
+
+    let migrations = Migrations::default()
+      .with_migration(Migration::from_function(2, "insert", |tx| {
+          tx.query_drop("INSERT INTO run_migrate (id) VALUES (1)")?;
+          tx.query_drop("INSERT INTO run_migrate (id) VALUES (2)")
+        })
+        .with_down_sql(&["DELETE FROM run_migrate"]))
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE run_migrate ( id INTEGER )"])
+        .with_down_sql(&["DROP TABLE run_migrate"]));
+
+    // Migrations applied once, in version order
+    assert!(migrations.migrate_to(s, 1)? == vec![1]);
+    assert!(count(s)? == 0);
+    assert!(migrations.migrate(s)? == vec![2]);
+    assert!(migrations.migrate(s)?.is_empty());
+    assert!(count(s)? == 2);
+    let applied = migrations.applied(s)?;
+    assert!(applied.len() == 2 && applied[0].name.eq("create") && applied[1].version == 2);
+
+    // Failed migration rolled back
+    let failing = Migrations::default()
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE run_migrate ( id INTEGER )"]))
+      .with_migration(Migration::from_function(2, "insert", |_| Ok(())))
+      .with_migration(Migration::from_function(3, "fail", |tx| {
+        tx.query_drop("INSERT INTO run_migrate (id) VALUES (3)")?;
+        tx.query_drop("SELECT FROM WHERE run_migrate")
+      }));
+    assert!(failing.migrate(s).is_err());
+    assert!(count(s)? == 2 && migrations.applied(s)?.len() == 2);
+
+    // Modified, unregistered, out of order and duplicate migrations refused
+    let modified = Migrations::default()
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE run_migrate ( id BIGINT )"]))
+      .with_migration(Migration::from_function(2, "insert", |_| Ok(())));
+    assert!(matches!(modified.migrate(s), Err(Error::MigrationModified(1))));
+    let unregistered = Migrations::default()
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE run_migrate ( id INTEGER )"]));
+    assert!(matches!(unregistered.migrate(s), Err(Error::MigrationNotRegistered(2))));
+    let out_of_order = Migrations::default()
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE run_migrate ( id INTEGER )"]))
+      .with_migration(Migration::from_function(2, "insert", |_| Ok(())))
+      .with_migration(Migration::from_sql(3, "late", &[]))
+      .with_migration(Migration::from_sql(0, "early", &[]));
+    assert!(matches!(out_of_order.migrate(s), Err(Error::MigrationOutOfOrder(0))));
+    let duplicate = Migrations::default()
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE run_migrate ( id INTEGER )"]))
+      .with_migration(Migration::from_sql(1, "create again", &[]));
+    assert!(matches!(duplicate.migrate(s), Err(Error::MigrationDuplicateVersion(1))));
+
+    // Down migrations
+    let irreversible = Migrations::default()
+      .with_migration(Migration::from_sql(1, "create", &["CREATE TABLE run_migrate ( id INTEGER )"]))
+      .with_migration(Migration::from_function(2, "insert", |_| Ok(())));
+    assert!(matches!(irreversible.revert_to(s, 0), Err(Error::MigrationIrreversible(2))));
+    assert!(migrations.revert_to(s, 1)? == vec![2]);
+    assert!(count(s)? == 0);
+    assert!(migrations.revert_to(s, 0)? == vec![1]);
+    assert!(migrations.applied(s)?.is_empty());
+    assert!(migrations.migrate(s)? == vec![1, 2]);
+
+    s.query_drop("DROP TABLE run_migrate")?;
+    s.query_drop(format!("DROP TABLE {}", migrate::MIGRATIONS_TABLE))?;
+    Ok(())
+  }
+
+  pub fn run_error_classification<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    s.query_drop("DROP TABLE IF EXISTS run_error_classification_child")?;
+    s.query_drop("DROP TABLE IF EXISTS run_error_classification")?;
+    if let traits::Flavor::SQLite = s.flavor() { s.query_drop("PRAGMA foreign_keys = ON")?; }
+    s.query_drop("CREATE TABLE run_error_classification ( id INTEGER PRIMARY KEY, name VARCHAR(10) NOT NULL UNIQUE )")?;
+    s.query_drop("CREATE TABLE run_error_classification_child ( parent_id INTEGER REFERENCES run_error_classification(id) )")?;
+    s.execute_with_params("INSERT INTO run_error_classification (id, name) VALUES (1, 'Jane')", &())?;
+
+    let r = s.execute_with_params("INSERT INTO run_error_classification (id, name) VALUES (2, 'Jane')", &());
+    assert!(matches!(r.as_ref().map_err(Error::inner), Err(Error::UniqueViolation(_))));
+    let r = s.execute_with_params("INSERT INTO run_error_classification (id, name) VALUES (1, 'John')", &());
+    assert!(matches!(r.as_ref().map_err(Error::inner), Err(Error::UniqueViolation(_))));
+    let r = s.execute_with_params("INSERT INTO run_error_classification (id, name) VALUES (3, NULL)", &());
+    assert!(matches!(r.as_ref().map_err(Error::inner), Err(Error::NotNullViolation(_))));
+    let r = s.execute_with_params("INSERT INTO run_error_classification_child (parent_id) VALUES (4)", &());
+    assert!(matches!(r.as_ref().map_err(Error::inner), Err(Error::ForeignKeyViolation(_))));
+    let r = s.query_drop("SELECT FROM WHERE run_error_classification");
+    assert!(matches!(r.as_ref().map_err(Error::inner), Err(Error::SyntaxError(_))));
+    assert!(r.is_err_and(|e| ! e.is_transient()));
+
+    s.query_drop("DROP TABLE run_error_classification_child")?;
+    s.query_drop("DROP TABLE run_error_classification")?;
+    Ok(())
+  }
+  pub fn run_statement_error<S, R>(s: &mut S) -> Result<()>
+  where S: traits::Connection<R>,
+        R: traits::Row,
+  {
+    s.query_drop("DROP TABLE IF EXISTS run_statement_error")?;
+    s.query_drop("CREATE TABLE run_statement_error ( id INTEGER PRIMARY KEY, name VARCHAR(10) )")?;
+    s.execute_with_params("INSERT INTO run_statement_error (id, name) VALUES (1, 'Jane')", &())?;
+
+    let r = s.execute_with_params("INSERT INTO run_statement_error (id, name) VALUES (?, ?)", &(1i64, "John"));
+    assert!(matches!(&r, Err(Error::Statement { sql, params: Some(params), source }) 
+      if sql.eq("INSERT INTO run_statement_error (id, name) VALUES (?, ?)")
+      && matches!(params.as_slice(), [traits::Param::BigInt(1), traits::Param::Text(name)] if name.eq("John"))
+      && matches!(source.as_ref(), Error::UniqueViolation(_))));
+    assert!(r.is_err_and(|e| e.to_string().contains("John")));
+
+    let r = s.query("SELECT unknown FROM run_statement_error");
+    assert!(matches!(&r, Err(Error::Statement { sql, params: Some(params), .. }) if sql.eq("SELECT unknown FROM run_statement_error") && params.is_empty()));
+
+    s.query_drop("DROP TABLE run_statement_error")?;
+    Ok(())
+  }
 }
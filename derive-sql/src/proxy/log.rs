@@ -8,6 +8,7 @@ where T: traits::Connection<R>,
 {
   conn: &'a mut T,
   level: ::log::Level,
+  redact_params: bool,
   phantom_r: std::marker::PhantomData<R>,
 }
 
@@ -16,7 +17,7 @@ where T: traits::Connection<R>,
       R: traits::Row,
 {
   pub fn from_connection_level(conn: &'a mut T, level: ::log::Level) -> Log<T, R> {
-    Log { conn, level, phantom_r: std::marker::PhantomData, }
+    Log { conn, level, redact_params: false, phantom_r: std::marker::PhantomData, }
   }
 
   pub fn inner_mut(&mut self) -> &mut T { &mut self.conn }
@@ -26,6 +27,21 @@ where T: traits::Connection<R>,
     self
   }
 
+  /// Omit parameters values from the `Error::Statement` context of errors returned by the connection - for example
+  /// when parameters may contain personal information. Default to `false`.
+  pub fn with_redact_params(mut self, redact_params: bool) -> Log<'a, T, R> {
+    self.redact_params = redact_params;
+    self
+  }
+
+  /// Attach the statement to the error of the result, omitting parameters when redacted
+  fn with_statement<U, P>(&self, r: Result<U>, statement: &str, params: &P) -> Result<U>
+  where P: traits::Params,
+  {
+    let r = r.with_statement(statement, params);
+    if self.redact_params { r.map_err(Error::without_params) } else { r }
+  }
+
   fn log(&self, statement: &str) {
     match self.level {
       ::log::Level::Error => ::log::error!("{statement}"),
@@ -41,7 +57,7 @@ impl<'a, T, R> std::convert::From<&'a mut T> for Log<'a, T, R>
 where T: traits::Connection<R>,
       R: traits::Row,
 {
-  fn from(conn: &'a mut T) -> Self { Log { conn, level: ::log::Level::Info, phantom_r: std::marker::PhantomData, } }
+  fn from(conn: &'a mut T) -> Self { Log { conn, level: ::log::Level::Info, redact_params: false, phantom_r: std::marker::PhantomData, } }
 }
   
 /*
@@ -66,38 +82,80 @@ where T: traits::Connection<R>,
 {
   fn flavor(&self) -> traits::Flavor { self.conn.flavor() }
 
-  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<()>
+  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<traits::ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: traits::Params,
   {
     self.log(query.as_ref());
-    self.conn.execute_with_params(query, params)
+    let r = self.conn.execute_with_params(query.as_ref(), params);
+    self.with_statement(r, query.as_ref(), params)
   }
 
-  fn execute_with_params_iterator<'b, S, I, P>(&mut self, query: S, params_iter: I) -> Result<()>
+  fn execute_with_params_iterator<'b, S, I, P>(&mut self, query: S, params_iter: I) -> Result<traits::ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: traits::Params + 'b,
         I: core::iter::IntoIterator<Item = &'b P>
   {
     self.log(query.as_ref());
-    self.conn.execute_with_params_iterator(query, params_iter)
+    let r = self.conn.execute_with_params_iterator(query.as_ref(), params_iter);
+    self.with_statement(r, query.as_ref(), &())
   }
 
-/*
-  fn execute_with_params_rows<S, P>(&mut self, query: S, params: &P) -> Result<Vec<R>>
+  fn query<S>(&mut self, query: S) -> Result<Vec<R>>
+  where S: std::convert::AsRef<str>,
+  {
+    self.log(query.as_ref());
+    let r = self.conn.query(query.as_ref());
+    self.with_statement(r, query.as_ref(), &())
+  }
+
+  fn query_with_params<S, P>(&mut self, query: S, params: &P) -> Result<Vec<R>>
   where S: std::convert::AsRef<str>,
         P: traits::Params,
   {
     self.log(query.as_ref());
-    self.conn.execute_with_params_rows(query, params)
+    let r = self.conn.query_with_params(query.as_ref(), params);
+    self.with_statement(r, query.as_ref(), params)
   }
-  */
 
-  fn query<S>(&mut self, query: S) -> Result<Vec<R>>
+  fn query_with_params_iter<S, P, F, U>(&mut self, query: S, params: &P, f: F) -> Result<U>
   where S: std::convert::AsRef<str>,
+        P: traits::Params,
+        F: FnOnce(traits::QueryIterator<'_, R>) -> Result<U>,
   {
     self.log(query.as_ref());
-    self.conn.query(query)
+    let r = self.conn.query_with_params_iter(query, params, f);
+    if self.redact_params { r.map_err(Error::without_params) } else { r }
+  }
+
+  fn begin(&mut self) -> Result<()> {
+    self.log("BEGIN");
+    self.conn.begin()
+  }
+
+  fn commit(&mut self) -> Result<()> {
+    self.log("COMMIT");
+    self.conn.commit()
+  }
+
+  fn rollback(&mut self) -> Result<()> {
+    self.log("ROLLBACK");
+    self.conn.rollback()
+  }
+
+  fn savepoint(&mut self, name: &str) -> Result<()> {
+    self.log(format!("SAVEPOINT {name}").as_str());
+    self.conn.savepoint(name)
+  }
+
+  fn release_savepoint(&mut self, name: &str) -> Result<()> {
+    self.log(format!("RELEASE SAVEPOINT {name}").as_str());
+    self.conn.release_savepoint(name)
+  }
+
+  fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+    self.log(format!("ROLLBACK TO SAVEPOINT {name}").as_str());
+    self.conn.rollback_to_savepoint(name)
   }
 }
 
@@ -0,0 +1,207 @@
+//! Connection recording statements and returning scripted results, to test code generic over `traits::Connection`
+//! without a database.
+//!
+//! ```rust
+//! use derive_sql::traits::{self, Connection, Value};
+//! use derive_sql::proxy::Mock;
+//!
+//! fn count_adults<C, R>(conn: &mut C) -> derive_sql::Result<Option<i64>>
+//! where C: traits::Connection<R>, R: traits::Row,
+//! {
+//!   conn.query_first_with_params_try_as_object("SELECT COUNT(*) FROM person WHERE age >= ?", &18i64)
+//! }
+//!
+//! let mut conn = Mock::from_flavor(traits::Flavor::PostgreSQL)
+//!   .with_rows("FROM person", vec![vec![Value::Integer(3)]]);
+//! assert!(count_adults(&mut conn).unwrap() == Some(3));
+//! assert!(conn.statements()[0].sql().eq("SELECT COUNT(*) FROM person WHERE age >= ?"));
+//! assert!(matches!(conn.statements()[0].params(), [traits::Param::BigInt(18)]));
+//! ```
+use super::*;
+
+/// Statement recorded by the `Mock` connection
+#[derive(Debug, Clone)]
+pub struct Statement {
+  sql: String,
+  params: Vec<traits::Param>,
+}
+
+impl Statement {
+  /// SQL statement as provided to the connection
+  pub fn sql(&self) -> &str { self.sql.as_str() }
+
+  /// Parameters bound to the statement
+  pub fn params(&self) -> &[traits::Param] { self.params.as_slice() }
+}
+
+/// Row returned by the `Mock` connection
+#[derive(Debug, Clone)]
+pub struct MockRow {
+  values: Vec<traits::Value>,
+}
+
+impl std::convert::From<Vec<traits::Value>> for MockRow {
+  fn from(values: Vec<traits::Value>) -> Self { MockRow { values } }
+}
+
+impl traits::Row for MockRow {
+  fn get_value(&self, i: usize) -> Option<Result<traits::Value>> {
+    self.values.get(i).map(|v| Ok(v.clone()))
+  }
+}
+
+enum Response {
+  Rows(Vec<MockRow>),
+  ExecuteResult(traits::ExecuteResult),
+  Error(String),
+}
+
+/// Connection recording the statements executed along with their parameters. Results are scripted per
+/// statement pattern: the response of the first pattern contained in the statement is returned. Queries
+/// not matching any pattern return no rows and `execute` statements return an empty `ExecuteResult`.
+pub struct Mock {
+  flavor: traits::Flavor,
+  statements: Vec<Statement>,
+  responses: Vec<(String, Response)>,
+}
+
+impl Mock {
+  /// Create a connection of the given flavor
+  pub fn from_flavor(flavor: traits::Flavor) -> Mock {
+    Mock { flavor, statements: Vec::new(), responses: Vec::new() }
+  }
+
+  /// Return `rows` for queries containing `pattern`
+  pub fn with_rows(mut self, pattern: &str, rows: Vec<Vec<traits::Value>>) -> Mock {
+    self.responses.push((pattern.to_string(), Response::Rows(rows.into_iter().map(MockRow::from).collect())));
+    self
+  }
+
+  /// Return `result` for `execute` statements containing `pattern`
+  pub fn with_execute_result(mut self, pattern: &str, result: traits::ExecuteResult) -> Mock {
+    self.responses.push((pattern.to_string(), Response::ExecuteResult(result)));
+    self
+  }
+
+  /// Fail statements containing `pattern` with `Error::Misc(message)`
+  pub fn with_error(mut self, pattern: &str, message: &str) -> Mock {
+    self.responses.push((pattern.to_string(), Response::Error(message.to_string())));
+    self
+  }
+
+  /// Statements recorded, in the order in which they have been executed
+  pub fn statements(&self) -> &[Statement] { self.statements.as_slice() }
+
+  /// SQL of the statements recorded, in the order in which they have been executed
+  pub fn sql(&self) -> Vec<&str> { self.statements.iter().map(|s| s.sql()).collect() }
+
+  /// Clear the statements recorded
+  pub fn clear(&mut self) { self.statements.clear(); }
+
+  fn record<P>(&mut self, sql: &str, params: &P) -> Result<Option<&Response>>
+  where P: traits::Params,
+  {
+    self.statements.push(Statement { sql: sql.to_string(), params: params.as_vec_params()? });
+    match self.responses.iter().find(|(pattern, _)| sql.contains(pattern.as_str())).map(|(_, response)| response) {
+      Some(Response::Error(message)) => Err(Error::Misc(message.clone())),
+      response => Ok(response),
+    }
+  }
+
+  fn record_execute<P>(&mut self, sql: &str, params: &P) -> Result<traits::ExecuteResult>
+  where P: traits::Params,
+  {
+    match self.record(sql, params)? {
+      Some(Response::ExecuteResult(result)) => Ok(*result),
+      _ => Ok(traits::ExecuteResult::default()),
+    }
+  }
+
+  fn record_query<P>(&mut self, sql: &str, params: &P) -> Result<Vec<MockRow>>
+  where P: traits::Params,
+  {
+    match self.record(sql, params)? {
+      Some(Response::Rows(rows)) => Ok(rows.clone()),
+      _ => Ok(Vec::new()),
+    }
+  }
+}
+
+impl traits::Connection<MockRow> for Mock {
+  fn flavor(&self) -> traits::Flavor { self.flavor }
+
+  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<traits::ExecuteResult>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
+  {
+    self.record_execute(query.as_ref(), params)
+  }
+
+  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<traits::ExecuteResult>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params + 'a,
+        I: core::iter::IntoIterator<Item = &'a P>,
+  {
+    params_iter.into_iter()
+    .try_fold(traits::ExecuteResult::default(), |r, params| Ok(r.and(self.record_execute(query.as_ref(), params)?)))
+  }
+
+  fn query<S>(&mut self, query: S) -> Result<Vec<MockRow>>
+  where S: std::convert::AsRef<str>,
+  {
+    self.record_query(query.as_ref(), &())
+  }
+
+  fn query_with_params<S, P>(&mut self, query: S, params: &P) -> Result<Vec<MockRow>>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
+  {
+    self.record_query(query.as_ref(), params)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_records_statements_and_params() -> Result<()> {
+    use traits::{Connection, Update};
+    use structs::Field;
+
+    struct Statement {}
+    impl traits::UpdateStatement for Statement {
+      fn update_stmt(&self) -> Result<String> { Ok("UPDATE person SET name = $1".to_string()) }
+    }
+
+    let mut conn = Mock::from_flavor(traits::Flavor::PostgreSQL)
+      .with_execute_result("UPDATE person", traits::ExecuteResult::from_rows_affected_last_insert_id(2, None));
+    let r = Statement {}.update_with_filter(&mut conn, &Field::from("name").eq("John"), &"Jane".to_string())?;
+    assert!(r.rows_affected() == 2);
+    assert!(conn.sql() == vec!["UPDATE person SET name = $1 WHERE name = $2"]);
+    assert!(matches!(conn.statements()[0].params(), [traits::Param::Text(a), traits::Param::Text(b)] if a.eq("Jane") && b.eq("John")));
+
+    conn.clear();
+    conn.execute_with_params_iterator("INSERT INTO person (name) VALUES ($1)", ["Jane", "John"].iter())?;
+    assert!(conn.statements().len() == 2);
+    Ok(())
+  }
+
+  #[test]
+  fn it_returns_scripted_rows_and_errors() -> Result<()> {
+    use traits::{Connection, Value};
+
+    let mut conn = Mock::from_flavor(traits::Flavor::SQLite)
+      .with_rows("FROM person", vec![vec![Value::Text("Jane".to_string())], vec![Value::Text("John".to_string())]])
+      .with_error("FROM artist", "table not found");
+    let names: Vec<String> = conn.query_try_as_object("SELECT name FROM person")?;
+    assert!(names == vec!["Jane".to_string(), "John".to_string()]);
+    let names: Vec<String> = conn.query_try_as_object("SELECT name FROM artwork")?;
+    assert!(names.is_empty());
+    assert!(conn.query("SELECT name FROM artist").is_err());
+
+    conn.begin_transaction()?.commit()?;
+    assert!(conn.sql().ends_with(&["BEGIN", "COMMIT"]));
+    Ok(())
+  }
+}
@@ -161,63 +161,117 @@ impl Transaction for ::mysql::PooledConn {
   }
 }
 
+/// Retrieve the number of rows affected and last inserted identifier from the result of a statement
+fn execute_result<P>(r: &::mysql::QueryResult<'_, '_, '_, P>) -> Result<traits::ExecuteResult>
+where P: ::mysql::prelude::Protocol,
+{
+  Ok(traits::ExecuteResult::from_rows_affected_last_insert_id(
+    r.affected_rows(),
+    r.last_insert_id().filter(|id| *id > 0).map(|id| id.try_into()).transpose()?,
+  ))
+}
+
+/// Prepare the statement and execute it for each set of parameters
+fn execute_prepared<T, P>(conn: &mut T, query: &str, params_arr: Vec<(&P, Vec<::mysql::Value>)>) -> Result<traits::ExecuteResult>
+where T: ::mysql::prelude::Queryable,
+      P: traits::Params,
+{
+  let statement = conn.prep(query).map_err(Error::from).with_statement(query, &())?;
+  let mut result = traits::ExecuteResult::default();
+  for (params, values) in params_arr {
+    let r = conn.exec_iter(&statement, values).map_err(Error::from)
+      .and_then(|r| execute_result(&r))
+      .with_statement(query, params)?;
+    result = result.and(r);
+  }
+  Ok(result)
+}
+
 impl<T> traits::Connection<Row> for T
 where T: ::mysql::prelude::Queryable + Transaction,
 {
   fn flavor(&self) -> traits::Flavor { traits::Flavor::MySQL }
 
-  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<()>
+  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<traits::ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: traits::Params,
   {
-    let params = params.as_vec_params()?
-    .into_iter()
-    .map(|p| std::convert::TryInto::<::mysql::Value>::try_into(p))
-    .collect::<Result<Vec<::mysql::Value>>>()?;
-    self.exec_drop(query, params)?;
-    Ok(())
+    (|| {
+      let params = params.as_vec_params()?
+      .into_iter()
+      .map(|p| std::convert::TryInto::<::mysql::Value>::try_into(p))
+      .collect::<Result<Vec<::mysql::Value>>>()?;
+      let r = self.exec_iter(query.as_ref(), params)?;
+      execute_result(&r)
+    })().with_statement(query.as_ref(), params)
   }
 
-  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<()>
+  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<traits::ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: traits::Params + 'a,
         I: core::iter::IntoIterator<Item = &'a P>
   {
-    use ::mysql::prelude::Queryable;
     let params_arr = params_iter
       .into_iter()
       .map(|params| 
         params.as_vec_params()?.into_iter()
         .map(|p| std::convert::TryInto::<::mysql::Value>::try_into(p))
         .collect::<Result<Vec<::mysql::Value>>>()
+        .map(|values| (params, values))
       )
-      .collect::<Result<Vec<Vec<::mysql::Value>>>>()?;
+      .collect::<Result<Vec<(&P, Vec<::mysql::Value>)>>>()?;
 
+    // Statements run in their own transaction, rolled back when dropped on failure. Within a transaction, statements
+    // are issued on the `traits::Transaction` guard which uses a savepoint instead.
     let mut conn = self.start_transaction(::mysql::TxOpts::default())?;
-    let statement = conn.prep(query)?;
-    conn.exec_batch(statement, params_arr.into_iter())?;
+    let result = execute_prepared(&mut conn, query.as_ref(), params_arr)?;
     conn.commit()?;
-    Ok(())
+    Ok(result)
   }
 
-/*
-  fn execute_with_params_rows<S, P>(&mut self, query: S, params: &P) -> Result<Vec<Row>>
+  fn query<S>(&mut self, query: S) -> Result<Vec<Row>>
+  where S: std::convert::AsRef<str>
+  {
+    self.query_map(query.as_ref(), |r: Row| r).map_err(Error::from).with_statement(query.as_ref(), &())
+  }
+
+  fn query_with_params<S, P>(&mut self, query: S, params: &P) -> Result<Vec<Row>>
   where S: std::convert::AsRef<str>,
         P: traits::Params,
   {
-    let params = params.as_vec_params()?
-    .into_iter()
-    .map(|p| std::convert::TryInto::<::mysql::Value>::try_into(p))
-    .collect::<Result<Vec<::mysql::Value>>>()?;
-    Ok( self.exec_map(query, params, |r: Row| r)? )
+    (|| {
+      let params = params.as_vec_params()?
+      .into_iter()
+      .map(std::convert::TryInto::<::mysql::Value>::try_into)
+      .collect::<Result<Vec<::mysql::Value>>>()?;
+      Ok( self.exec_map(query.as_ref(), params, |r: Row| r)? )
+    })().with_statement(query.as_ref(), params)
   }
-  */
 
-  fn query<S>(&mut self, query: S) -> Result<Vec<Row>>
-  where S: std::convert::AsRef<str>
+  fn query_with_params_iter<S, P, F, U>(&mut self, query: S, params: &P, f: F) -> Result<U>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
+        F: FnOnce(traits::QueryIterator<'_, Row>) -> Result<U>,
   {
-    Ok( self.query_map(query, |r: Row| r)? )
+    let values = params.as_vec_params()?
+    .into_iter()
+    .map(std::convert::TryInto::<::mysql::Value>::try_into)
+    .collect::<Result<Vec<::mysql::Value>>>()?;
+    let rows = self.exec_iter(query.as_ref(), values).map_err(Error::from).with_statement(query.as_ref(), params)?;
+    f(Box::new(rows.map(|row| Ok(Row { row: row? }))))
   }
+
+  fn begin(&mut self) -> Result<()> { Ok(::mysql::prelude::Queryable::query_drop(self, "START TRANSACTION")?) }
+
+  fn commit(&mut self) -> Result<()> { Ok(::mysql::prelude::Queryable::query_drop(self, "COMMIT")?) }
+
+  fn rollback(&mut self) -> Result<()> { Ok(::mysql::prelude::Queryable::query_drop(self, "ROLLBACK")?) }
+
+  fn savepoint(&mut self, name: &str) -> Result<()> { Ok(::mysql::prelude::Queryable::query_drop(self, format!("SAVEPOINT {name}"))?) }
+
+  fn release_savepoint(&mut self, name: &str) -> Result<()> { Ok(::mysql::prelude::Queryable::query_drop(self, format!("RELEASE SAVEPOINT {name}"))?) }
+
+  fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> { Ok(::mysql::prelude::Queryable::query_drop(self, format!("ROLLBACK TO SAVEPOINT {name}"))?) }
 }
 
 #[cfg(test)]
@@ -247,5 +301,119 @@ mod tests {
     proxy_test::run_with_date(&mut conn)?;
     Ok(())
   }
+
+  #[test]
+  fn test_transaction() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_transaction(&mut conn)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_filter_with_params() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_filter_with_params(&mut conn)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_query_iter() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_query_iter(&mut conn)?;
+    proxy_test::run_query_iter(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_query_with_params() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_query_with_params(&mut conn)?;
+    proxy_test::run_query_with_params(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_execute_result() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_execute_result(&mut conn)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_many_params() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_many_params(&mut conn)?;
+    proxy_test::run_many_params(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_returning() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_returning(&mut conn)?;
+    proxy_test::run_returning(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_upsert() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_upsert(&mut conn)?;
+    proxy_test::run_upsert(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_select_by_keys() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_select_by_keys(&mut conn)?;
+    proxy_test::run_select_by_keys(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_migrate() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_migrate(&mut conn)?;
+    proxy_test::run_migrate(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_error_classification() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_error_classification(&mut conn)?;
+    Ok(())
+  }
+  #[test]
+  fn test_statement_error() -> Result<()> {
+    let mut conn = ::mysql::Conn::new(
+      ::mysql::Opts::from_url("mysql://test@localhost/simpledb").unwrap()
+    )?;
+    proxy_test::run_statement_error(&mut conn)?;
+    Ok(())
+  }
 }
 
@@ -82,99 +82,134 @@ impl traits::Row for Row {
   }
 }
 
-fn execute<'a, P>(statement: &mut rusqlite::Statement<'a>, params: &P) -> Result<()>
+/// Execute the statement and return the number of rows affected
+fn execute<'a, P>(statement: &mut rusqlite::Statement<'a>, params: &P) -> Result<u64>
 where P: traits::Params,
 {
     let params: Vec<traits::Param> = params.as_vec_params()?;
-    let _ = match params.len() {
-      0 => statement.execute(())?,
-      1 => statement.execute([ &params[0] ] )?,
-      2 => statement.execute([ &params[0], &params[1] ] )?,
-      3 => statement.execute([ &params[0], &params[1], &params[2], ] )?,
-      4 => statement.execute([ &params[0], &params[1], &params[2], &params[3], ] )?,
-      5 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], ] )?,
-      6 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], ] )?,
-      7 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], ] )?,
-      8 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], ] )?,
-      9 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], ] )?,
-     10 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], ] )?,
-     11 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], &params[10], ] )?,
-     12 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], &params[10], &params[11], ] )?,
-     13 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], &params[10], &params[11], &params[12], ] )?,
-     14 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], &params[10], &params[11], &params[12], &params[13], ] )?,
-     15 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], &params[10], &params[11], &params[12], &params[13], &params[14], ] )?,
-     16 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], &params[10], &params[11], &params[12], &params[13], &params[14], &params[15], ] )?,
-     17 => statement.execute([ &params[0], &params[1], &params[2], &params[3], &params[4], &params[5], &params[6], &params[7], &params[8], &params[9], &params[10], &params[11], &params[12], &params[13], &params[14], &params[15], &params[16], ] )?,
-      // _ => { self.conn.execute(query, params.iter().collect::<Vec<&traits::Param>>().as_slice())?; },
-      _ => { return Err(Error::MaximumNumberOfParametersExceeded(17, params.len())); },
-    };
-    Ok(())
+    let rows_affected = statement.execute(rusqlite::params_from_iter(params.iter()))?;
+    Ok(rows_affected.try_into()?)
+}
+
+/// Reset `last_insert_rowid` before executing a statement, returning the value to restore if the statement inserts no row.
+/// `last_insert_rowid` is left unchanged by statements not inserting rows - including an upsert taking the update branch - and
+/// is reset such that a row inserted with the same identifier as the previous insert - in another table for example - is detected.
+fn reset_last_insert_id(conn: &rusqlite::Connection) -> i64 {
+  let before = conn.last_insert_rowid();
+  unsafe { rusqlite::ffi::sqlite3_set_last_insert_rowid(conn.handle(), 0); }
+  before
+}
+
+/// Retrieve the identifier of the row inserted by the statement, if any, or restore the value of `last_insert_rowid` returned by
+/// `reset_last_insert_id` otherwise. A row explicitly inserted with identifier `0` is not reported.
+fn last_insert_id(conn: &rusqlite::Connection, before: i64) -> Option<i64> {
+  match conn.last_insert_rowid() {
+    0 => {
+      unsafe { rusqlite::ffi::sqlite3_set_last_insert_rowid(conn.handle(), before); }
+      None
+    },
+    id => Some(id),
+  }
 }
 
 impl traits::Connection<Row> for rusqlite::Connection
 {
   fn flavor(&self) -> traits::Flavor { traits::Flavor::SQLite }
 
-  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<()>
+  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<traits::ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: traits::Params,
   {
-    let mut statement = self.prepare(query.as_ref())?;
-    execute(&mut statement, params)
+    let before = reset_last_insert_id(self);
+    let rows_affected = self.prepare(query.as_ref()).map_err(Error::from)
+      .and_then(|mut statement| execute(&mut statement, params))
+      .with_statement(query.as_ref(), params);
+    let last_insert_id = last_insert_id(self, before);
+    Ok(traits::ExecuteResult::from_rows_affected_last_insert_id(rows_affected?, last_insert_id))
   }
 
-  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<()>
+  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<traits::ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: traits::Params + 'a,
         I: core::iter::IntoIterator<Item = &'a P>
   {
-    let tx = self.transaction()?;
-    {
-      let mut statement = tx.prepare(query.as_ref())?;
-      for params in params_iter { execute(&mut statement, params)?; }
-    }
-    tx.commit()?;
-    Ok(())
+    // Use a savepoint so that the statements can run within a transaction started with `begin`
+    let before = reset_last_insert_id(self);
+    let rows_affected = (|| -> Result<u64> {
+      let sp = self.savepoint()?;
+      let mut rows_affected = 0;
+      {
+        let mut statement = sp.prepare(query.as_ref()).map_err(Error::from).with_statement(query.as_ref(), &())?;
+        for params in params_iter { rows_affected += execute(&mut statement, params).with_statement(query.as_ref(), params)?; }
+      }
+      sp.commit()?;
+      Ok(rows_affected)
+    })();
+    let last_insert_id = last_insert_id(self, before);
+    Ok(traits::ExecuteResult::from_rows_affected_last_insert_id(rows_affected?, last_insert_id))
   }
 
-/*
-  fn execute_with_params_rows<S, P>(&mut self, query: S, params: &P) -> Result<Vec<Row>>
-  where S: std::convert::AsRef<str>,
-        P: traits::Params,
+  fn query<S>(&mut self, query: S) -> Result<Vec<Row>>
+  where S: std::convert::AsRef<str>
   {
-    let mut statement = self.prepare(query.as_ref())?;
-    let column_count = statement.column_count();
+    use fallible_iterator::FallibleIterator;
 
-    let f = |row: &rusqlite::Row<'_>| { Ok((column_count, row).try_into()?) };
-    let params: Vec<traits::Param> = params.as_vec_params()?;
-    let r = match params.len() {
-      0 => statement.query_map((), f)?,
-      1 => statement.query_map([ &params[0] ], f )?,
-      2 => statement.query_map([ &params[0], &params[1] ], f )?,
-      // _ => { self.conn.execute(query, params.iter().collect::<Vec<&traits::Param>>().as_slice())?; },
-      _ => { return Err(Error::NotImplemented); },
-    }
-    .collect::<rusqlite::Result<Vec<Row>>>()?;
+    (|| {
+      let mut statement = self.prepare(query.as_ref())?;
+      let column_count = statement.column_count();
 
-    Ok(r)
-  }
-  */
+      let r = statement.query(())?
+      .map(|row| Ok((column_count, row).try_into()?) )
+      .collect::<Vec<Row>>()?;
 
+      Ok(r)
+    })().with_statement(query.as_ref(), &())
+  }
 
-  fn query<S>(&mut self, query: S) -> Result<Vec<Row>>
-  where S: std::convert::AsRef<str>
+  fn query_with_params<S, P>(&mut self, query: S, params: &P) -> Result<Vec<Row>>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
   {
     use fallible_iterator::FallibleIterator;
 
-    let mut statement = self.prepare(query.as_ref())?;
-    let column_count = statement.column_count();
+    (|| {
+      let mut statement = self.prepare(query.as_ref())?;
+      let column_count = statement.column_count();
 
-    let r = statement.query(())?
-    .map(|row| Ok((column_count, row).try_into()?) )
-    .collect::<Vec<Row>>()?;
+      let params: Vec<traits::Param> = params.as_vec_params()?;
+      let r = statement.query(rusqlite::params_from_iter(params.iter()))?
+      .map(|row| (column_count, row).try_into() )
+      .collect::<Vec<Row>>()?;
 
-    Ok(r)
+      Ok(r)
+    })().with_statement(query.as_ref(), params)
   }
+
+  fn query_with_params_iter<S, P, F, T>(&mut self, query: S, params: &P, f: F) -> Result<T>
+  where S: std::convert::AsRef<str>,
+        P: traits::Params,
+        F: FnOnce(traits::QueryIterator<'_, Row>) -> Result<T>,
+  {
+    let mut statement = self.prepare(query.as_ref()).map_err(Error::from).with_statement(query.as_ref(), params)?;
+    let column_count = statement.column_count();
+
+    let values: Vec<traits::Param> = params.as_vec_params()?;
+    let rows = statement.query_map(rusqlite::params_from_iter(values.iter()), |row| (column_count, row).try_into())
+      .map_err(Error::from).with_statement(query.as_ref(), params)?;
+    f(Box::new(rows.map(|row| Ok(row?))))
+  }
+
+  fn begin(&mut self) -> Result<()> { Ok(self.execute_batch("BEGIN")?) }
+
+  fn commit(&mut self) -> Result<()> { Ok(self.execute_batch("COMMIT")?) }
+
+  fn rollback(&mut self) -> Result<()> { Ok(self.execute_batch("ROLLBACK")?) }
+
+  fn savepoint(&mut self, name: &str) -> Result<()> { Ok(self.execute_batch(format!("SAVEPOINT {name}").as_str())?) }
+
+  fn release_savepoint(&mut self, name: &str) -> Result<()> { Ok(self.execute_batch(format!("RELEASE SAVEPOINT {name}").as_str())?) }
+
+  fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> { Ok(self.execute_batch(format!("ROLLBACK TO SAVEPOINT {name}").as_str())?) }
 }
 
 #[cfg(test)]
@@ -203,4 +238,124 @@ mod tests {
     proxy_test::run_with_date(&mut conn)?;
     Ok(())
   }
+
+  #[test]
+  fn test_transaction() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_transaction(&mut conn)?;
+    assert!(conn.is_autocommit());
+    Ok(())
+  }
+
+  #[test]
+  fn test_filter_with_params() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_filter_with_params(&mut conn)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_query_iter() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_query_iter(&mut conn)?;
+    proxy_test::run_query_iter(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_query_with_params() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_query_with_params(&mut conn)?;
+    proxy_test::run_query_with_params(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_execute_result() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_execute_result(&mut conn)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_many_params() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_many_params(&mut conn)?;
+    proxy_test::run_many_params(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_returning() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_returning(&mut conn)?;
+    proxy_test::run_returning(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_upsert() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_upsert(&mut conn)?;
+    proxy_test::run_upsert(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_select_by_keys() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_select_by_keys(&mut conn)?;
+    proxy_test::run_select_by_keys(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_migrate() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_migrate(&mut conn)?;
+    proxy_test::run_migrate(&mut proxy::Log::from(&mut conn))?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_error_classification() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_error_classification(&mut conn)?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_busy_error() -> Result<()> {
+    use traits::Connection;
+    let path = std::env::temp_dir().join(format!("derive_sql_busy_{}.db", std::process::id()));
+    let mut conn = rusqlite::Connection::open(&path)?;
+    let mut other = rusqlite::Connection::open(&path)?;
+    other.busy_timeout(std::time::Duration::ZERO)?;
+    conn.query_drop("CREATE TABLE test_busy ( id INTEGER )")?;
+    conn.query_drop("BEGIN EXCLUSIVE")?;
+    let r = other.query_drop("SELECT id FROM test_busy");
+    assert!(matches!(r.as_ref().map_err(Error::inner), Err(Error::Busy(_))));
+    assert!(r.is_err_and(|e| e.is_transient()));
+    conn.query_drop("ROLLBACK")?;
+    drop(conn); drop(other);
+    std::fs::remove_file(&path).map_err(|e| Error::Misc(e.to_string()))?;
+    Ok(())
+  }
+  #[test]
+  fn test_statement_error() -> Result<()> {
+    use traits::Connection;
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    proxy_test::run_statement_error(&mut conn)?;
+
+    let r = proxy::Log::from(&mut conn).with_redact_params(true)
+      .execute_with_params("INSERT INTO unknown_table (name) VALUES (?)", &"Jane");
+    assert!(matches!(&r, Err(Error::Statement { params: None, .. })));
+    assert!(r.is_err_and(|e| ! e.to_string().contains("Jane")));
+
+    // Driver error reported as source only
+    let r = conn.query_drop("SELECT name FROM unknown_table");
+    assert!(r.is_err_and(|e| e.to_string().eq("Statement `SELECT name FROM unknown_table` failed") 
+      && std::error::Error::source(&e).is_some_and(|source| source.to_string().contains("no such table"))));
+    Ok(())
+  }
 }
@@ -192,6 +192,45 @@ mod tests {
     Ok(())
   }
 
+  #[test]
+  fn it_escapes_quotes_in_string_clause() -> Result<()> {
+    use traits::FlavoredFilter;
+    let conn = traits::tests::SQLiteFlavoredConnection {};
+
+    assert!(Field::from("name").eq("O'Brien").filter::<_, Row>(&conn)?.eq("`name` = 'O''Brien'"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_binds_escaped_values_as_params() -> Result<()> {
+    use traits::FlavoredFilter;
+    let conn = traits::tests::SQLiteFlavoredConnection {};
+
+    let mut params = Vec::new();
+    assert!(Field::from("key_str").eq("O'Brien").filter_with_params::<_, Row>(&conn, &mut params)?.eq("`key_str` = ?"));
+    assert!(matches!(params.as_slice(), [traits::Param::Text(v)] if v.eq("O'Brien")));
+
+    let mut params = Vec::new();
+    assert!(Field::from("key").eq(1u32).filter_with_params::<_, Row>(&conn, &mut params)?.eq("`key` = 1"));
+    assert!(params.is_empty());
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_numbers_placeholders_following_existing_params() -> Result<()> {
+    use traits::FlavoredFilter;
+    let conn = traits::tests::PostgreSQLFlavoredConnection {};
+
+    let mut params = vec![traits::Param::Int(1)];
+    let filter = filter::And::from((Field::from("a").eq("x"), Field::from("b").ne("y"), Field::from("c").is_none()));
+    assert!(filter.filter_with_params::<_, Row>(&conn, &mut params)?.eq("( a = $2 AND b != $3 AND c IS NULL )"));
+    assert!(params.len() == 3);
+
+    Ok(())
+  }
+
   #[test]
   fn it_display_correct_order_clause() -> Result<()> {
     use traits::FlavoredOrder;
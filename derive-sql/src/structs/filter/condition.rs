@@ -34,12 +34,13 @@ where T: std::fmt::Display
   }
 }
 
-impl<T> traits::FlavoredFilter for Condition<T>
+impl<T> Condition<T>
 where T: std::fmt::Display
 {
-  /// Return the `WHERE` clause associated with the condition
-  fn filter<C, R>(&self, conn: &C) -> Result<String>
+  /// Return the `WHERE` clause associated with the condition using the provided function to write the value
+  fn filter_with_value<C, R, V>(&self, conn: &C, mut value: V) -> Result<String>
   where C: traits::Connection<R>, R: traits::Row,
+        V: FnMut(&Value<T>) -> Result<String>,
   {
     let flavor = conn.flavor();
     let label = if let Some(table) = &self.table {
@@ -53,17 +54,48 @@ where T: std::fmt::Display
     let r = match &self.operator {
       Operator::IsNull          => format!("{label} IS NULL"),
       Operator::IsNotNull       => format!("{label} IS NOT NULL"),
-      Operator::Equal(v)        => format!("{label} = {v}"),
-      Operator::NotEqual(v)     => format!("{label} != {v}"),
-      Operator::GreaterThan(v)  => format!("{label} > {v}"),
-      Operator::GreaterEqual(v) => format!("{label} >= {v}"),
-      Operator::LowerThan(v)    => format!("{label} < {v}"),
-      Operator::LowerEqual(v)   => format!("{label} <= {v}"),
+      Operator::Equal(v)        => format!("{label} = {v}", v = value(v)?),
+      Operator::NotEqual(v)     => format!("{label} != {v}", v = value(v)?),
+      Operator::GreaterThan(v)  => format!("{label} > {v}", v = value(v)?),
+      Operator::GreaterEqual(v) => format!("{label} >= {v}", v = value(v)?),
+      Operator::LowerThan(v)    => format!("{label} < {v}", v = value(v)?),
+      Operator::LowerEqual(v)   => format!("{label} <= {v}", v = value(v)?),
     };
     Ok(r)
   }
 }
 
+impl<T> traits::FlavoredFilter for Condition<T>
+where T: std::fmt::Display
+{
+  /// Return the `WHERE` clause associated with the condition with escaped values written as string literals
+  /// quoted for the flavor of the connection
+  fn filter<C, R>(&self, conn: &C) -> Result<String>
+  where C: traits::Connection<R>, R: traits::Row,
+  {
+    let flavor = conn.flavor();
+    self.filter_with_value(conn, |v| Ok(v.to_sql(&flavor)))
+  }
+
+  /// Return the `WHERE` clause associated with the condition with escaped values bound as text parameters, as
+  /// they are otherwise written as string literals
+  fn filter_with_params<C, R>(&self, conn: &C, params: &mut Vec<traits::Param>) -> Result<String>
+  where C: traits::Connection<R>, R: traits::Row,
+  {
+    let flavor = conn.flavor();
+    self.filter_with_value(conn, |v| {
+      match v {
+        Value::Escaped(v) => {
+          let placeholder = flavor.value(params.len())?;
+          params.push(traits::Param::Text(v.to_string()));
+          Ok(placeholder)
+        },
+        Value::Raw(v) => Ok(v.to_string()),
+      }
+    })
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -84,6 +116,39 @@ mod tests {
     Ok(())
   }
 
+  #[test]
+  fn it_writes_string_literals_for_the_flavor() -> Result<()> {
+    use traits::FlavoredFilter;
+    let value = "\\' OR 1=1 -- ";
+
+    let conn = proxy::Mock::from_flavor(traits::Flavor::MySQL);
+    assert!(Field::from("name").eq(value).filter(&conn)?.eq("`name` = '\\\\'' OR 1=1 -- '"));
+    let conn = proxy::Mock::from_flavor(traits::Flavor::PostgreSQL);
+    assert!(Field::from("name").eq(value).filter(&conn)?.eq("name = '\\'' OR 1=1 -- '"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_filters_on_values_implementing_display() -> Result<()> {
+    use traits::FlavoredFilter;
+    struct Name(&'static str);
+    impl std::fmt::Display for Name {
+      fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.0) }
+    }
+
+    let condition = Condition::from_label_operator("name".to_string(), Operator::Equal(Value::Escaped(Name("\\' OR 1=1 -- "))));
+    let conn = proxy::Mock::from_flavor(traits::Flavor::MySQL);
+    assert!(condition.filter(&conn)?.eq("`name` = '\\\\'' OR 1=1 -- '"));
+    let mut params = Vec::new();
+    assert!(condition.filter_with_params(&conn, &mut params)?.eq("`name` = ?"));
+    assert!(matches!(params.as_slice(), [traits::Param::Text(v)] if v.eq("\\' OR 1=1 -- ")));
+
+    // Written without flavor, values are quoted as for MySQL
+    assert!(Value::Escaped("\\'").to_string().eq("'\\\\'''"));
+    Ok(())
+  }
+
   #[cfg(test)]
   fn it_display_correct_clause_for_string() -> Result<()> {
     use traits::FlavoredFilter;
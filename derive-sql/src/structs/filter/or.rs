@@ -75,6 +75,17 @@ where T: traits::FlavoredFilter, U: traits::FlavoredFilter, V: traits::FlavoredF
     };
     Ok(r)
   }
+
+  fn filter_with_params<C, R>(&self, conn: &C, params: &mut Vec<traits::Param>) -> Result<String>
+  where C: traits::Connection<R>, R: traits::Row,
+  {
+    let mut clauses = vec![self.t.filter_with_params(conn, params)?, self.u.filter_with_params(conn, params)?];
+    if let Some(v) = &self.v { clauses.push(v.filter_with_params(conn, params)?); }
+    if let Some(w) = &self.w { clauses.push(w.filter_with_params(conn, params)?); }
+    if let Some(x) = &self.x { clauses.push(x.filter_with_params(conn, params)?); }
+    if let Some(y) = &self.y { clauses.push(y.filter_with_params(conn, params)?); }
+    Ok(format!("( {} )", clauses.join(" OR ")))
+  }
 }
 
 #[cfg(test)]
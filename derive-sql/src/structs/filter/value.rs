@@ -1,6 +1,9 @@
 //! Define value properties for use in filter
-// use super::*;
+use super::*;
 
+/// Value used in a filter condition. `Escaped` values are bound as text parameters when the filter is
+/// used in a statement - or written as a string literal quoted for the flavor of the connection when the
+/// statement is only generated, ie with `to_sql`. `Raw` values are written as is in the statement.
 pub enum Value<T>
 where T: std::fmt::Display
 {
@@ -8,17 +11,29 @@ where T: std::fmt::Display
   Raw(T),
 }
 
-impl<T> std::fmt::Display for Value<T>
+impl<T> Value<T>
 where T: std::fmt::Display
 {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+  /// Write the value in a statement for the given flavor, with `Escaped` values quoted using `Flavor::string_literal`
+  pub fn to_sql(&self, flavor: &traits::Flavor) -> String {
     match self {
-      Value::Escaped(v) => write!(f, "'{v}'"),
-      Value::Raw(v) => write!(f, "{v}"),
+      Value::Escaped(v) => flavor.string_literal(v.to_string().as_str()),
+      Value::Raw(v) => v.to_string(),
     }
   }
 }
 
+/// Write the value without knowing the flavor of the connection: `Escaped` values are quoted as for MySQL - quotes and
+/// backslashes doubled - which is safe with all flavors but alters values containing backslashes for SQLite and PostgreSQL.
+/// Prefer `to_sql`.
+impl<T> std::fmt::Display for Value<T>
+where T: std::fmt::Display
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{v}", v = self.to_sql(&traits::Flavor::MySQL))
+  }
+}
+
 impl std::convert::From<String> for Value<String> {
   fn from(v: String) -> Self {
     Value::Escaped(v)
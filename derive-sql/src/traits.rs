@@ -2,8 +2,14 @@
 use super::*;
 
 mod params; pub use params::{Params, Param, ToParam};
-mod row; pub use row::{Value, Row, TryFromRefRow, }; // Row2, RefTryInto, };
-mod flavor; pub use flavor::{Flavor};
+mod row; pub use row::{Value, Row, TryFromRefRow, TryFromValue, }; // Row2, RefTryInto, };
+mod flavor; pub use flavor::{Flavor, SqlType, RawType};
+mod column_type; pub use column_type::SqlColumnType;
+mod transaction; pub use transaction::Transaction;
+mod execute_result; pub use execute_result::ExecuteResult;
+
+/// Iterator over the results of a query. Results are retrieved from the database as the iterator is consumed.
+pub type QueryIterator<'a, T> = Box<dyn Iterator<Item = Result<T>> + 'a>;
 
 /// Generic trait to be implemented by SQL drivers (or proxy to SQL drivers). This trait is used
 /// to provide the basis of the functionalities on which the crate rely
@@ -13,44 +19,72 @@ where R: Row,
   /// Returns flavor of SQL
   fn flavor(&self) -> Flavor;
 
-  /// Implements an `execute` statement
-  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<()>
+  /// Implements an `execute` statement. Returns the number of rows affected and the last inserted row identifier
+  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: Params;
 
-  /// Implements an `execute` statement over an iterator of parameters
-  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<()>
+  /// Implements an `execute` statement over an iterator of parameters. Returns the total number of rows affected
+  /// and the last inserted row identifier. Drivers run the statements in their own transaction: within a transaction,
+  /// issue the statements on the guard returned by `begin_transaction` which runs them in a savepoint.
+  fn execute_with_params_iterator<'a, S, I, P>(&mut self, query: S, params_iter: I) -> Result<ExecuteResult>
   where S: std::convert::AsRef<str>,
         P: Params + 'a,
         I: core::iter::IntoIterator<Item = &'a P>;
 
-  /*
-  /// Implements an `execute` statement returning the modified elements
-  fn execute_with_params_rows<S, P>(&mut self, query: S, params: &P) -> Result<Vec<R>>
-  where S: std::convert::AsRef<str>,
-        P: Params;
-        */
-
   /// Implements a `query` statement returning a list of results stored as `Row`
   fn query<S>(&mut self, query: S) -> Result<Vec<R>>
   where S: std::convert::AsRef<str>;
 
+  /// Implements a `query` statement with parameters returning a list of results stored as `Row`.
+  /// Default to an error such that `Connection` implementations predating this method keep compiling: the drivers and
+  /// proxies of this crate implement it.
+  fn query_with_params<S, P>(&mut self, _query: S, _params: &P) -> Result<Vec<R>>
+  where S: std::convert::AsRef<str>,
+        P: Params,
+  {
+    Err("query_with_params not implemented".into())
+  }
+
+  /// Implements a `query` statement with parameters for which the rows are provided as an iterator to `f`. The iterator
+  /// borrows the underlying statement and is only available for the duration of `f`.
+  /// Default to collecting the results of `query_with_params`: drivers override this method to retrieve rows as the iterator is consumed.
+  fn query_with_params_iter<S, P, F, T>(&mut self, query: S, params: &P, f: F) -> Result<T>
+  where S: std::convert::AsRef<str>,
+        P: Params,
+        F: FnOnce(QueryIterator<'_, R>) -> Result<T>,
+  {
+    let rows = self.query_with_params(query, params)?;
+    f(Box::new(rows.into_iter().map(Ok)))
+  }
+
   // Implemented methods:
-  /*
-  /// `execute` statement retuning the list of results as object of the given type `T`
-  fn execute_with_params_try_as_object<S, P, T>(&mut self, query: S, params: &P) -> Result<Vec<T>>
+  /// `query` statement with parameters returning list of objects of type `T`
+  ///
+  /// ```rust
+  /// # #[cfg(feature = "sqlite")]
+  /// # fn wrapper() {
+  /// use derive_sql::traits::Connection;
+  ///
+  /// let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+  /// conn.query_drop("CREATE TABLE example_params (id INTEGER, name TEXT)").unwrap();
+  /// conn.execute_with_params_iterator("INSERT INTO example_params (id, name) VALUES (?, ?)", 
+  ///   [(1i64, "O'Brien".to_string()), (2i64, "Jane".to_string())].iter()).unwrap();
+  ///
+  /// let ids: Vec<i64> = conn.query_with_params_try_as_object("SELECT id FROM example_params WHERE name = ?", &"O'Brien").unwrap();
+  /// assert!(ids.len() == 1 && ids[0] == 1);
+  /// # }
+  /// ```
+  fn query_with_params_try_as_object<S, P, T>(&mut self, query: S, params: &P) -> Result<Vec<T>>
   where S: std::convert::AsRef<str>,
         P: Params,
         T: TryFromRefRow<R>,
   {
-    Ok(
-    self.execute_with_params_rows(query, params)?
+    self.query_with_params(query, params)?
     .iter()
-    .map(|r: &R| Ok(T::try_from(r)?))
-    .collect::<Result<Vec<T>>>()?
-    )
+    .map(|r: &R| T::try_from(r))
+    .collect::<Result<Vec<T>>>()
   }
-  */
 
 /*
   /// `execute` statement retuning the list of results as object of the given type `T`
@@ -68,30 +102,26 @@ where R: Row,
   }
   */
 
-/*
-  /// `execute` statement returning only the first item in the list
-  fn execute_with_params_first<S, P>(&mut self, query: S, params: P) -> Result<Option<R>>
+  /// `query` statement with parameters returning only the first item in the list
+  fn query_first_with_params<S, P>(&mut self, query: S, params: &P) -> Result<Option<R>>
   where S: std::convert::AsRef<str>,
         P: Params,
   {
-    let r = self.execute_with_params(query, params)?;
-    Ok(r.into_iter().nth(0))
+    Ok(self.query_with_params(query, params)?.into_iter().nth(0))
   }
 
-  /// `execute` statement returning the first item in the list as an object of the given type `T`
-  fn execute_with_params_first_try_as_object<S, P, T, E>(&mut self, query: S, params: P) -> Result<Option<T>>
+  /// `query` statement with parameters returning only the first item as an object of type `T`
+  fn query_first_with_params_try_as_object<S, P, T>(&mut self, query: S, params: &P) -> Result<Option<T>>
   where S: std::convert::AsRef<str>,
         P: Params,
-        T: for<'a> std::convert::TryFrom<&'a R, Error=E>,
-        error::Error: std::convert::From<E>,
+        T: TryFromRefRow<R>,
   {
-    if let Some(r) = self.execute_with_params_first::<_, _>(query, params)? {
-      Ok(Some((&r).try_into()?))
+    if let Some(r) = self.query_first_with_params(query, params)? {
+      Ok(Some(T::try_from(&r)?))
     } else {
       Ok(None)
     }
   }
-  */
 
 /*
   /// `execute` statement returning the first item in the list as an object of the given type `T`
@@ -145,6 +175,39 @@ where R: Row,
   }
   */
 
+  /// `query` statement for which the rows are provided as an iterator to `f`
+  ///
+  /// ```rust
+  /// # #[cfg(feature = "sqlite")]
+  /// # fn wrapper() {
+  /// use derive_sql::traits::Connection;
+  ///
+  /// let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+  /// conn.query_drop("CREATE TABLE example_iter (id INTEGER)").unwrap();
+  /// conn.execute_with_params_iterator("INSERT INTO example_iter (id) VALUES (?)", [1i64, 2i64, 3i64].iter()).unwrap();
+  ///
+  /// let sum: i64 = conn.query_iter_try_as_object("SELECT id FROM example_iter", |ids: derive_sql::traits::QueryIterator<i64>| {
+  ///   ids.sum()
+  /// }).unwrap();
+  /// assert!(sum == 6);
+  /// # }
+  /// ```
+  fn query_iter<S, F, T>(&mut self, query: S, f: F) -> Result<T>
+  where S: std::convert::AsRef<str>,
+        F: FnOnce(QueryIterator<'_, R>) -> Result<T>,
+  {
+    self.query_with_params_iter(query, &(), f)
+  }
+
+  /// `query` statement for which the rows are converted to objects of type `U` as they are consumed from the iterator provided to `f`
+  fn query_iter_try_as_object<S, U, F, T>(&mut self, query: S, f: F) -> Result<T>
+  where S: std::convert::AsRef<str>,
+        U: TryFromRefRow<R>,
+        F: FnOnce(QueryIterator<'_, U>) -> Result<T>,
+  {
+    self.query_iter(query, |rows| f(Box::new(rows.map(|r| U::try_from(&r?)))))
+  }
+
   /// `query` statement returning only the first item in the list
   fn query_first<S>(&mut self, query: S) -> Result<Option<R>>
   where S: std::convert::AsRef<str>,
@@ -185,23 +248,70 @@ where R: Row,
     self.query_first::<_>(query)?;
     Ok(())
   }
+
+  /// Start a transaction. Prefer `begin_transaction` which returns a guard rolling back the
+  /// transaction when dropped without being committed. Statements issued directly on the
+  /// connection via `execute_with_params_iterator` may manage their own transaction with some drivers.
+  fn begin(&mut self) -> Result<()> { self.query_drop("BEGIN") }
+
+  /// Commit the current transaction
+  fn commit(&mut self) -> Result<()> { self.query_drop("COMMIT") }
+
+  /// Rollback the current transaction
+  fn rollback(&mut self) -> Result<()> { self.query_drop("ROLLBACK") }
+
+  /// Create a savepoint with the given name in the current transaction
+  fn savepoint(&mut self, name: &str) -> Result<()> { self.query_drop(format!("SAVEPOINT {name}")) }
+
+  /// Release the nominated savepoint
+  fn release_savepoint(&mut self, name: &str) -> Result<()> { self.query_drop(format!("RELEASE SAVEPOINT {name}")) }
+
+  /// Rollback to the nominated savepoint
+  fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> { self.query_drop(format!("ROLLBACK TO SAVEPOINT {name}")) }
+
+  /// Start a transaction and return a guard. The transaction is rolled back when the guard
+  /// is dropped without having been committed. The guard implements `Connection` and calling
+  /// `begin_transaction` on the guard creates a nested transaction using a savepoint.
+  fn begin_transaction(&mut self) -> Result<Transaction<'_, Self, R>>
+  where Self: Sized,
+  {
+    Transaction::from_connection(self)
+  }
 }
 
 mod sql;    // pub use sql::Sql;
 mod table;  pub use table::{Table, TableStatement, TableFlavoredStatement};
 mod insert; pub use insert::{Insert, InsertMultiple, InsertStatement, InsertFlavoredStatement};
-mod select; pub use select::{Select as SelectV2, SelectStatement, SelectFlavoredStatement};
+mod select; pub use select::{Select as SelectV2, SelectByKeys, SelectStatement, SelectFlavoredStatement};
 mod update; pub use update::{Update, UpdateStatement, UpdateFlavoredStatement};
 mod delete; pub use delete::{Delete, DeleteStatement, DeleteFlavoredStatement};
 mod filter; pub use filter::{Filter, FlavoredFilter};
 mod order;  pub use order::{Order, FlavoredOrder};
+mod upsert; pub use upsert::{Upsert, UpsertStatement, UpsertFlavoredStatement};
+mod returning; pub use returning::{InsertReturning, UpdateReturning, DeleteReturning, ReturningStatement, ReturningFlavoredStatement};
 
 /// Combine a flavored statement with optional filter, order, limit and offset to return full statement
 fn statement_with_conn_filter_order_limit_offset_options<C, R, F, O>(statement: String, conn: &C, filter: Option<&F>, order: Option<&O>, limit: Option<usize>, offset: Option<usize>) -> Result<String>
 where C: Connection<R>, R: Row, F: FlavoredFilter, O: FlavoredOrder,
+{
+  let filter = filter.map(|filter| filter.filter(conn)).transpose()?;
+  statement_with_conn_clause_order_limit_offset_options(statement, conn, filter, order, limit, offset)
+}
+
+/// Combine a flavored statement with optional filter, order, limit and offset to return full statement. Filter values
+/// are appended to `params` and replaced by placeholders in the statement
+fn statement_with_conn_params_filter_order_limit_offset_options<C, R, F, O>(statement: String, conn: &C, params: &mut Vec<Param>, filter: Option<&F>, order: Option<&O>, limit: Option<usize>, offset: Option<usize>) -> Result<String>
+where C: Connection<R>, R: Row, F: FlavoredFilter, O: FlavoredOrder,
+{
+  let filter = filter.map(|filter| filter.filter_with_params(conn, params)).transpose()?;
+  statement_with_conn_clause_order_limit_offset_options(statement, conn, filter, order, limit, offset)
+}
+
+/// Combine a flavored statement with optional `WHERE` clause, order, limit and offset to return full statement
+fn statement_with_conn_clause_order_limit_offset_options<C, R, O>(statement: String, conn: &C, filter: Option<String>, order: Option<&O>, limit: Option<usize>, offset: Option<usize>) -> Result<String>
+where C: Connection<R>, R: Row, O: FlavoredOrder,
 {
   let statement = if let Some(filter) = filter { 
-    let filter = filter.filter(conn)?;
     if ! filter.is_empty() { format!("{statement} WHERE {filter}") } 
     else { statement }
   } else { statement };
@@ -217,7 +327,18 @@ where C: Connection<R>, R: Row, F: FlavoredFilter, O: FlavoredOrder,
   } else { statement };
   Ok(statement)
 }
+
 /// Combine a statement with optional filter, order, limit and offset to return full statement
+fn statement_with_conn_returning<C, R, S>(statement: String, conn: &C, returning: &S) -> Result<String>
+where C: Connection<R>, R: Row, S: ReturningFlavoredStatement,
+{
+  match conn.flavor() {
+    Flavor::SQLite
+    | Flavor::PostgreSQL => Ok(format!("{statement} {returning}", returning = returning.returning_stmt(conn)?)),
+    Flavor::MySQL => Err(Error::ReturningNotSupported(conn.flavor().to_string())),
+  }
+}
+
 fn statement_with_filter_order_limit_offset_options<F, O>(statement: String, filter: Option<&F>, order: Option<&O>, limit: Option<usize>, offset: Option<usize>) -> Result<String>
 where F: Filter, O: Order,
 {
@@ -248,16 +369,39 @@ pub mod tests {
   where R: traits::Row
   {
     fn flavor(&self) -> Flavor { Flavor::SQLite }
-    fn execute_with_params<S, P>(&mut self, _query: S, _params: &P) -> Result<()>
+    fn execute_with_params<S, P>(&mut self, _query: S, _params: &P) -> Result<ExecuteResult>
     where S: std::convert::AsRef<str>, P: Params,
     { Err("command not available for SQLiteFlavoredConnection".into()) }
-    fn execute_with_params_iterator<'a, S, I, P>(&mut self, _query: S, _params_iter: I) -> Result<()>
+    fn execute_with_params_iterator<'a, S, I, P>(&mut self, _query: S, _params_iter: I) -> Result<ExecuteResult>
     where S: std::convert::AsRef<str>, P: Params + 'a, 
           I: core::iter::IntoIterator<Item = &'a P>,
     { Err("command not available for SQLiteFlavoredConnection".into()) }
     fn query<S>(&mut self, _query: S) -> Result<Vec<R>>
     where S: std::convert::AsRef<str>,
     { Err("command not available for SQLiteFlavoredConnection".into()) }
+    fn query_with_params<S, P>(&mut self, _query: S, _params: &P) -> Result<Vec<R>>
+    where S: std::convert::AsRef<str>, P: Params,
+    { Err("command not available for SQLiteFlavoredConnection".into()) }
+  }
+
+  pub struct PostgreSQLFlavoredConnection {}
+  impl<R> Connection<R> for PostgreSQLFlavoredConnection 
+  where R: traits::Row
+  {
+    fn flavor(&self) -> Flavor { Flavor::PostgreSQL }
+    fn execute_with_params<S, P>(&mut self, _query: S, _params: &P) -> Result<ExecuteResult>
+    where S: std::convert::AsRef<str>, P: Params,
+    { Err("command not available for PostgreSQLFlavoredConnection".into()) }
+    fn execute_with_params_iterator<'a, S, I, P>(&mut self, _query: S, _params_iter: I) -> Result<ExecuteResult>
+    where S: std::convert::AsRef<str>, P: Params + 'a, 
+          I: core::iter::IntoIterator<Item = &'a P>,
+    { Err("command not available for PostgreSQLFlavoredConnection".into()) }
+    fn query<S>(&mut self, _query: S) -> Result<Vec<R>>
+    where S: std::convert::AsRef<str>,
+    { Err("command not available for PostgreSQLFlavoredConnection".into()) }
+    fn query_with_params<S, P>(&mut self, _query: S, _params: &P) -> Result<Vec<R>>
+    where S: std::convert::AsRef<str>, P: Params,
+    { Err("command not available for PostgreSQLFlavoredConnection".into()) }
   }
 
   pub struct Row {}
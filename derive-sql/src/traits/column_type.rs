@@ -0,0 +1,73 @@
+//! Conversion of user-defined types - such as newtypes or enums - to and from SQL columns
+//!
+//! `DeriveSqlStatement` falls back to `SqlColumnType` for field types it does not know:
+//!
+//! ```rust
+//! use derive_sql::traits::{self, SqlColumnType, Insert, SelectV2, Table};
+//! use derive_sql::DeriveSqlStatement;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Email(String);
+//!
+//! impl SqlColumnType for Email {
+//!   fn raw_type(_flavor: &traits::Flavor) -> derive_sql::Result<traits::RawType> { Ok(traits::RawType::Text) }
+//!   fn to_param(&self) -> derive_sql::Result<traits::Param> { Ok(traits::Param::Text(self.0.clone())) }
+//!   fn try_from_value(v: traits::Value) -> derive_sql::Result<Self> { Ok(Email(traits::TryFromValue::try_from(v)?)) }
+//! }
+//!
+//! #[derive(DeriveSqlStatement)]
+//! struct Contact {
+//!   name: String,
+//!   email: Email,
+//!   backup_email: Option<Email>,
+//! }
+//!
+//! let mut conn = derive_sql::proxy::Mock::from_flavor(traits::Flavor::SQLite)
+//!   .with_rows("FROM `contact`", vec![vec![
+//!     traits::Value::Text("Jo".to_string()), traits::Value::Text("jo@example.com".to_string()), traits::Value::Null,
+//!   ]]);
+//! let db = SqlContact::default();
+//! db.create(&mut conn).unwrap();
+//! assert!(conn.sql()[0].contains("`email` TEXT NOT NULL, `backup_email` TEXT NULL"));
+//!
+//! db.insert(&mut conn, &Contact { name: "Jo".to_string(), email: Email("jo@example.com".to_string()), backup_email: None }).unwrap();
+//! assert!(matches!(conn.statements()[1].params(), [_, traits::Param::Text(email), traits::Param::Null] if email.eq("jo@example.com")));
+//!
+//! let contacts: Vec<Contact> = db.select(&mut conn).unwrap();
+//! assert!(contacts[0].email == Email("jo@example.com".to_string()) && contacts[0].backup_email.is_none());
+//! ```
+use super::*;
+
+/// Trait to be implemented by types stored in a single SQL column
+pub trait SqlColumnType: Sized {
+  /// SQL type of the column for the given flavor
+  fn raw_type(flavor: &Flavor) -> Result<RawType>;
+
+  /// Convert to the parameter bound to SQL statements
+  fn to_param(&self) -> Result<Param>;
+
+  /// Convert from the value retrieved from the database
+  fn try_from_value(v: Value) -> Result<Self>;
+}
+
+impl<T> SqlColumnType for Option<T>
+where T: SqlColumnType,
+{
+  fn raw_type(flavor: &Flavor) -> Result<RawType> { T::raw_type(flavor) }
+
+  fn to_param(&self) -> Result<Param> {
+    match self {
+      Some(v) => v.to_param(),
+      None    => Ok(Param::Null),
+    }
+  }
+
+  fn try_from_value(v: Value) -> Result<Self> {
+    match v {
+      Value::Null => Ok(None),
+#[cfg(feature = "mysql")]
+      Value::MysqlValue(::mysql::Value::NULL) => Ok(None),
+      v => Ok(Some(T::try_from_value(v)?)),
+    }
+  }
+}
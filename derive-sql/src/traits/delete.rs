@@ -23,6 +23,20 @@ pub trait DeleteFlavoredStatement {
     statement_with_conn_filter_order_limit_offset_options(self.delete_stmt(conn)?,
       conn, Some(filter), Some(order), Some(limit), Some(offset))
   }
+
+  /// Delete statement and parameters with optionals filter, order, limit and offset. Filter values are
+  /// returned as parameters
+  fn delete_with_filter_order_limit_offset_options_params_stmt<C, R, F, O>(&self, conn: &C, filter: Option<&F>, order: Option<&O>, limit: Option<usize>, offset: Option<usize>) -> Result<(String, Vec<Param>)>
+  where C: Connection<R>,
+        R: Row,
+        F: traits::FlavoredFilter, 
+        O: traits::FlavoredOrder,
+  {
+    let mut params = Vec::new();
+    let statement = statement_with_conn_params_filter_order_limit_offset_options(self.delete_stmt(conn)?,
+      conn, &mut params, filter, order, limit, offset)?;
+    Ok((statement, params))
+  }
 }
 
 pub trait DeleteStatement {
@@ -59,14 +73,14 @@ where C: Connection<R>,
       R: Row,
 {
   /// Delete all items of type stored in database
-  fn delete(&self, conn: &mut C) -> Result<()>;
+  fn delete(&self, conn: &mut C) -> Result<ExecuteResult>;
 
   /// Delete all items of type matching the filtering criteria stored in database
-  fn delete_with_filter<F>(&self, conn: &mut C, filter: &F) -> Result<()>
+  fn delete_with_filter<F>(&self, conn: &mut C, filter: &F) -> Result<ExecuteResult>
   where F: traits::FlavoredFilter;
 
   /// Delete `limit` items after nominated `offset` items of type matching the filtering criteria and ordered in accordance with order
   /// statement
-  fn delete_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize) -> Result<()>
+  fn delete_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize) -> Result<ExecuteResult>
   where F: traits::FlavoredFilter, O: FlavoredOrder;
 }
@@ -0,0 +1,32 @@
+//! Information returned by the database following the execution of a statement
+
+/// Outcome of an `execute` statement: number of rows affected and identifier of the
+/// last row inserted when provided by the driver.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExecuteResult {
+  rows_affected: u64,
+  last_insert_id: Option<i64>,
+}
+
+impl ExecuteResult {
+  /// Create from the number of rows affected and the identifier of the last row inserted
+  pub fn from_rows_affected_last_insert_id(rows_affected: u64, last_insert_id: Option<i64>) -> ExecuteResult {
+    ExecuteResult { rows_affected, last_insert_id }
+  }
+
+  /// Number of rows inserted, updated or deleted by the statement
+  pub fn rows_affected(&self) -> u64 { self.rows_affected }
+
+  /// Identifier of the last row inserted - ie the generated key when inserting in a table with an
+  /// auto-generated primary key. Not available for PostgreSQL which requires a `RETURNING` clause.
+  pub fn last_insert_id(&self) -> Option<i64> { self.last_insert_id }
+
+  /// Combine with the outcome of a subsequent statement: rows affected are summed and the
+  /// last insert identifier of the subsequent statement is retained when available
+  pub fn and(self, other: ExecuteResult) -> ExecuteResult {
+    ExecuteResult {
+      rows_affected: self.rows_affected + other.rows_affected,
+      last_insert_id: other.last_insert_id.or(self.last_insert_id),
+    }
+  }
+}
@@ -5,6 +5,16 @@ pub trait FlavoredFilter {
   fn filter<C, R>(&self, conn: &C) -> Result<String>
   where C: Connection<R>,
         R: Row;
+
+  /// Returns the contents of a `WHERE` clause in which values are replaced by placeholders. The values
+  /// are appended to `params` and placeholders are numbered following the parameters already in `params`.
+  /// Default to the clause returned by `filter` without parameters.
+  fn filter_with_params<C, R>(&self, conn: &C, _params: &mut Vec<Param>) -> Result<String>
+  where C: Connection<R>,
+        R: Row,
+  {
+    self.filter(conn)
+  }
 }
 
 /// Trait to be implemented for filtering. Returns the contents of a `WHERE` clause.
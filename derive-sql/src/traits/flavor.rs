@@ -1,8 +1,9 @@
 use super::*;
 
-mod sql_type; use sql_type::{SqlType, RawType};
+mod sql_type; pub use sql_type::{SqlType, RawType};
 
 /// Enum to advise on the SQL flavor supported by the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Flavor {
   /// SQLite type connection
   SQLite,
@@ -62,17 +63,110 @@ impl Flavor {
     }
   }
 
-  /// Conver to SQL type
+  /// Maximum number of parameters bound to a statement: 32766 for SQLite - from version 3.32 - and 65535 for MySQL
+  /// and PostgreSQL
+  pub fn max_params(&self) -> usize {
+    match self {
+      Flavor::SQLite => 32766,
+      Flavor::MySQL
+      | Flavor::PostgreSQL => 65535,
+    }
+  }
+
+  /// Write the value as a quoted string literal. Quotes are doubled and - for MySQL, which treats backslashes as
+  /// escape characters by default - backslashes are doubled. Prefer binding values as parameters.
+  pub fn string_literal(&self, v: &str) -> String {
+    match self {
+      Flavor::SQLite
+      | Flavor::PostgreSQL => format!("'{v}'", v = v.replace('\'', "''")),
+      Flavor::MySQL => format!("'{v}'", v = v.replace('\\', "\\\\").replace('\'', "''")),
+    }
+  }
+
+  /// Clause appended to an `INSERT ... VALUES` statement to update `columns` of the existing row when the insert conflicts
+  /// with the primary key or unique constraint on `key`. Column names are expected as returned by `column`. For MySQL, the
+  /// inserted values are referred to using the row alias `new` - rather than the deprecated `VALUES()` function - which
+  /// requires MySQL 8.0.19 or later.
+  pub fn on_conflict_update(&self, key: &[String], columns: &[String]) -> Result<String> {
+    match self {
+      Flavor::SQLite
+      | Flavor::PostgreSQL => {
+        if columns.is_empty() { Ok(format!("ON CONFLICT ({key}) DO NOTHING", key = key.join(", "))) }
+        else {
+          Ok(format!("ON CONFLICT ({key}) DO UPDATE SET {columns}", key = key.join(", "),
+            columns = columns.iter().map(|c| format!("{c} = excluded.{c}")).collect::<Vec<String>>().join(", ")))
+        }
+      },
+      Flavor::MySQL => {
+        if columns.is_empty() { self.on_conflict_ignore(key) }
+        else {
+          Ok(format!("AS new ON DUPLICATE KEY UPDATE {columns}",
+            columns = columns.iter().map(|c| format!("{c} = new.{c}")).collect::<Vec<String>>().join(", ")))
+        }
+      },
+    }
+  }
+
+  /// Clause appended to an insert statement to leave the existing row untouched when the insert conflicts with
+  /// the primary key or unique constraint on `key`. Column names are expected as returned by `column`.
+  pub fn on_conflict_ignore(&self, key: &[String]) -> Result<String> {
+    match self {
+      Flavor::SQLite
+      | Flavor::PostgreSQL => Ok(format!("ON CONFLICT ({key}) DO NOTHING", key = key.join(", "))),
+      Flavor::MySQL => {
+        let column = key.first().ok_or(Error::Misc("Conflict key requires at least one column".to_string()))?;
+        Ok(format!("ON DUPLICATE KEY UPDATE {column} = {column}"))
+      },
+    }
+  }
+
+  /// Convert to nullable SQL type
   pub fn sql_type(&self, ty: &str) -> Result<SqlType> {
-    /* match nullable {
+    self.sql_type_nullable(ty, true)
+  }
+
+  /// Convert to SQL type, ie the raw type followed by `NULL` or `NOT NULL`
+  pub fn sql_type_nullable(&self, ty: &str, nullable: bool) -> Result<SqlType> {
+    match nullable {
       true  => Ok(SqlType::Nullable(self.raw_type(ty)?)),
       false => Ok(SqlType::NonNullable(self.raw_type(ty)?)),
-    }*/
-    Ok(SqlType::Nullable(self.raw_type(ty)?))
+    }
+  }
+
+  /// Convert the type implementing `SqlColumnType` to SQL type
+  pub fn column_sql_type<T>(&self, nullable: bool) -> Result<SqlType>
+  where T: SqlColumnType,
+  {
+    match nullable {
+      true  => Ok(SqlType::Nullable(T::raw_type(self)?)),
+      false => Ok(SqlType::NonNullable(T::raw_type(self)?)),
+    }
+  }
+
+  /// SQL type of an integer primary key generated by the database on insert, ie `INTEGER PRIMARY KEY AUTOINCREMENT` for SQLite,
+  /// `AUTO_INCREMENT` for MySQL and `GENERATED ALWAYS AS IDENTITY` for PostgreSQL
+  pub fn auto_increment_sql_type(&self, ty: &str) -> Result<SqlType> {
+    match self.raw_type(ty)? {
+      raw_type @ (RawType::SmallInt | RawType::Int | RawType::BigInt) => Ok(SqlType::AutoIncrementPrimaryKey(raw_type, *self)),
+      _ => Err(Error::SqlTypeNotSupported(self.to_string(), ty.to_string())),
+    }
   }
 
-  /// Convert to SQL raw type
-  fn raw_type(&self, ty: &str) -> Result<RawType> {
+  /// SQL type of a column storing JSON documents: `JSON` for MySQL, `JSONB` for PostgreSQL and `TEXT` for SQLite
+  pub fn json_sql_type(&self, nullable: bool) -> Result<SqlType> {
+    let raw_type = match self {
+      Flavor::SQLite     => RawType::Text,
+      Flavor::MySQL      => RawType::Json,
+      Flavor::PostgreSQL => RawType::Jsonb,
+    };
+    match nullable {
+      true  => Ok(SqlType::Nullable(raw_type)),
+      false => Ok(SqlType::NonNullable(raw_type)),
+    }
+  }
+
+  /// Convert the name of a Rust type - such as `i64` or `String` - to SQL raw type
+  pub fn raw_type(&self, ty: &str) -> Result<RawType> {
     match (self, ty) {
         (Flavor::SQLite,     "i8") 
       | (Flavor::MySQL,      "i8") 
@@ -133,16 +227,27 @@ impl Flavor {
 
         (Flavor::SQLite,     "DateTime") 
       | (Flavor::MySQL,      "DateTime") 
-      | (Flavor::PostgreSQL, "DateTime") 
       | (Flavor::SQLite,     "NaiveDateTime") 
       | (Flavor::MySQL,      "NaiveDateTime") 
-      | (Flavor::PostgreSQL, "NaiveDateTime") 
       => Ok(RawType::DateTime),
 
+        (Flavor::PostgreSQL, "DateTime") 
+      | (Flavor::PostgreSQL, "NaiveDateTime") 
+      => Ok(RawType::Timestamp),
+
         (Flavor::SQLite,     "NaiveDate") 
       | (Flavor::MySQL,      "NaiveDate") 
       | (Flavor::PostgreSQL, "NaiveDate") 
       => Ok(RawType::Date),
+
+        (Flavor::SQLite,     "Vec<u8>") 
+      => Ok(RawType::Blob),
+
+        (Flavor::MySQL,      "Vec<u8>") 
+      => Ok(RawType::LongBlob),
+
+        (Flavor::PostgreSQL, "Vec<u8>") 
+      => Ok(RawType::Bytea),
       
       _ => Err(Error::SqlTypeNotSupported(self.to_string(), ty.to_string())),
 
@@ -1,8 +1,11 @@
 use super::*;
 
+/// SQL type of a column, ie raw type and whether the column accepts `NULL` values
 pub enum SqlType {
   Nullable(RawType),
   NonNullable(RawType),
+  /// Primary key generated by the database on insert
+  AutoIncrementPrimaryKey(RawType, Flavor),
 }
 
 impl std::fmt::Display for SqlType {
@@ -10,10 +13,14 @@ impl std::fmt::Display for SqlType {
     match self {
       SqlType::Nullable(v) => write!(f, "{v} NULL"),
       SqlType::NonNullable(v) => write!(f, "{v} NOT NULL"),
+      SqlType::AutoIncrementPrimaryKey(v, Flavor::SQLite)     => write!(f, "{v} PRIMARY KEY AUTOINCREMENT"),
+      SqlType::AutoIncrementPrimaryKey(v, Flavor::MySQL)      => write!(f, "{v} NOT NULL AUTO_INCREMENT PRIMARY KEY"),
+      SqlType::AutoIncrementPrimaryKey(v, Flavor::PostgreSQL) => write!(f, "{v} GENERATED ALWAYS AS IDENTITY PRIMARY KEY"),
     }
   }
 }
 
+/// SQL data type
 pub enum RawType {
   SmallInt,
   Int,
@@ -23,7 +30,13 @@ pub enum RawType {
   Double,
   Text,
   DateTime,
+  Timestamp,
   Date,
+  Blob,
+  LongBlob,
+  Bytea,
+  Json,
+  Jsonb,
 }
 
 impl std::fmt::Display for RawType {
@@ -37,7 +50,13 @@ impl std::fmt::Display for RawType {
       RawType::Double   => write!(f, "FLOAT8"),
       RawType::Text     => write!(f, "TEXT"),
       RawType::DateTime => write!(f, "DATETIME"),
+      RawType::Timestamp => write!(f, "TIMESTAMP"),
       RawType::Date     => write!(f, "DATE"),
+      RawType::Blob     => write!(f, "BLOB"),
+      RawType::LongBlob => write!(f, "LONGBLOB"),
+      RawType::Bytea    => write!(f, "BYTEA"),
+      RawType::Json     => write!(f, "JSON"),
+      RawType::Jsonb    => write!(f, "JSONB"),
     }
   }
 }
@@ -25,13 +25,14 @@ pub trait Insert<C, R, T>
 where C: Connection<R>,
       R: Row,
 {
-  fn insert(&self, conn: &mut C, object: &T) -> Result<()>;
+  /// Insert the object. The returned result provides the generated key - when available - with `last_insert_id`
+  fn insert(&self, conn: &mut C, object: &T) -> Result<ExecuteResult>;
 }
 
 pub trait InsertMultiple<'a, C, R, T: 'a>
 where C: Connection<R>,
       R: Row,
 {
-  fn insert_multiple<I>(&self, conn: &mut C, objects: I) -> Result<()>
+  fn insert_multiple<I>(&self, conn: &mut C, objects: I) -> Result<ExecuteResult>
   where I: core::iter::IntoIterator<Item = &'a T>;
 }
@@ -2,6 +2,12 @@ use super::*;
 
 pub trait Params {
   fn as_vec_params(&self) -> Result<Vec<Param>>;
+
+  /// Parameters bound to insert statements. Default to `as_vec_params`
+  fn as_vec_insert_params(&self) -> Result<Vec<Param>> { self.as_vec_params() }
+
+  /// Parameters bound to update statements. Default to `as_vec_params`
+  fn as_vec_update_params(&self) -> Result<Vec<Param>> { self.as_vec_params() }
 }
 
 impl Params for () {
@@ -10,63 +16,43 @@ impl Params for () {
 impl<A> Params for A where A: ToParam, {
   fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.to_param()?]) }
 }
-impl<A, B> Params 
-for (A, B) 
-where A: ToParam, B: ToParam, 
-{
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?]) }
-}
-impl<A, B, C> Params 
-for (A, B, C) 
-where A: ToParam, B: ToParam, C: ToParam, 
-{
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?]) }
-}
-impl<A, B, C, D> Params 
-for (A, B, C, D) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, 
-{
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?]) }
-}
-impl<A, B, C, D, E> Params 
-for (A, B, C, D, E) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, E: ToParam, 
-{
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?, self.4.to_param()?]) }
-}
-impl<A, B, C, D, E, F> Params 
-for (A, B, C, D, E, F) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, E: ToParam, F: ToParam, {
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?, self.4.to_param()?, self.5.to_param()?]) }
-}
-impl<A, B, C, D, E, F, G> Params 
-for (A, B, C, D, E, F, G) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, E: ToParam, F: ToParam, G: ToParam, {
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?, self.4.to_param()?, self.5.to_param()?, self.6.to_param()?]) }
+/// Implement `Params` for a tuple of items implementing `ToParam`
+macro_rules! impl_params_for_tuple {
+  ( $( $t:ident $i:tt ),+ ) => {
+    impl< $( $t ),+ > Params
+    for ( $( $t, )+ )
+    where $( $t: ToParam, )+
+    {
+      fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![ $( self.$i.to_param()? ),+ ]) }
+    }
+  };
 }
-impl<A, B, C, D, E, F, G, H> Params 
-for (A, B, C, D, E, F, G, H) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, E: ToParam, F: ToParam, G: ToParam, H: ToParam, {
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?, self.4.to_param()?, self.5.to_param()?, self.6.to_param()?, self.7.to_param()?]) }
+
+/// Implement `Params` for the tuple of the items listed in brackets and for each tuple extended with the
+/// following items one at a time
+macro_rules! impl_params_for_tuples {
+  ( [ $( $t:ident $i:tt ),+ ] ) => {
+    impl_params_for_tuple!( $( $t $i ),+ );
+  };
+  ( [ $( $t:ident $i:tt ),+ ] $tn:ident $in:tt $( $rest:tt )* ) => {
+    impl_params_for_tuple!( $( $t $i ),+ );
+    impl_params_for_tuples!( [ $( $t $i, )+ $tn $in ] $( $rest )* );
+  };
 }
-impl<A, B, C, D, E, F, G, H, I> Params 
-for (A, B, C, D, E, F, G, H, I) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, E: ToParam, F: ToParam, G: ToParam, H: ToParam, I: ToParam, {
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?, self.4.to_param()?, self.5.to_param()?, self.6.to_param()?, self.7.to_param()?, self.8.to_param()?]) }
+
+impl_params_for_tuples!([T0 0, T1 1] T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11 T12 12 T13 13 T14 14 T15 15 T16 16 T17 17 T18 18 T19 19 T20 20 T21 21 T22 22 T23 23 T24 24 T25 25 T26 26 T27 27 T28 28 T29 29 T30 30 T31 31);
+
+impl Params for Vec<Param> {
+  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(self.clone()) }
 }
-impl<A, B, C, D, E, F, G, H, I, J> Params 
-for (A, B, C, D, E, F, G, H, I, J) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, E: ToParam, F: ToParam, G: ToParam, H: ToParam, I: ToParam, J: ToParam, {
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?, self.4.to_param()?, self.5.to_param()?, self.6.to_param()?, self.7.to_param()?, self.8.to_param()?, self.9.to_param()?]) }
+impl<T> Params for &[T] where T: ToParam, {
+  fn as_vec_params(&self) -> Result<Vec<Param>> { self.iter().map(|p| p.to_param()).collect() }
 }
-impl<A, B, C, D, E, F, G, H, I, J, K> Params 
-for (A, B, C, D, E, F, G, H, I, J, K) 
-where A: ToParam, B: ToParam, C: ToParam, D: ToParam, E: ToParam, F: ToParam, G: ToParam, H: ToParam, I: ToParam, J: ToParam, K: ToParam, {
-  fn as_vec_params(&self) -> Result<Vec<Param>> { Ok(vec![self.0.to_param()?, self.1.to_param()?, self.2.to_param()?, self.3.to_param()?, self.4.to_param()?, self.5.to_param()?, self.6.to_param()?, self.7.to_param()?, self.8.to_param()?, self.9.to_param()?, self.10.to_param()?]) }
+impl<T, const N: usize> Params for [T; N] where T: ToParam, {
+  fn as_vec_params(&self) -> Result<Vec<Param>> { self.iter().map(|p| p.to_param()).collect() }
 }
 
-
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Param {
   Null,
   Bytes(Vec<u8>),
@@ -79,6 +65,8 @@ pub enum Param {
   NaiveDate(chrono::naive::NaiveDate),
   NaiveDateTime(chrono::naive::NaiveDateTime),
   Bool(bool),
+#[cfg(feature = "json")]
+  Json(serde_json::Value),
 }
 
 #[cfg(feature = "postgres")]
@@ -98,6 +86,8 @@ impl ::postgres::types::ToSql for Param {
       Param::NaiveDate(v) => v.to_sql(ty, out),
       Param::NaiveDateTime(v) => v.to_sql(ty, out),
       Param::Bool(v)      => v.to_sql(ty, out),
+#[cfg(feature = "json")]
+      Param::Json(v)      => v.to_sql(ty, out),
     }
   }
 
@@ -120,6 +110,8 @@ impl ::postgres::types::ToSql for Param {
       Param::NaiveDate(v) => v.to_sql_checked(ty, out),
       Param::NaiveDateTime(v) => v.to_sql_checked(ty, out),
       Param::Bool(v)      => v.to_sql_checked(ty, out),
+#[cfg(feature = "json")]
+      Param::Json(v)      => v.to_sql_checked(ty, out),
     }
   }
 }
@@ -139,6 +131,8 @@ impl ::rusqlite::types::ToSql for Param {
       Param::NaiveDate(v) => v.to_sql(),
       Param::NaiveDateTime(v) => v.to_sql(),
       Param::Bool(v)   => v.to_sql(),
+#[cfg(feature = "json")]
+      Param::Json(v)   => Ok(::rusqlite::types::ToSqlOutput::from(v.to_string())),
     }
   }
 }
@@ -159,11 +153,14 @@ impl TryFrom<Param> for ::mysql::Value {
       Param::NaiveDate(v) => Ok(v.into()),
       Param::NaiveDateTime(v) => Ok(v.into()),
       Param::Bool(v)   => Ok((if v { 1 } else { 0 }).into()),
+#[cfg(feature = "json")]
+      Param::Json(v)   => Ok(v.to_string().into()),
     }
   }
 }
 
 pub trait ToParam        { fn to_param(&self) -> Result<Param>; }
+impl ToParam for Param   { fn to_param(&self) -> Result<Param> { Ok(self.clone()) } }
 impl ToParam for Vec<u8> { fn to_param(&self) -> Result<Param> { Ok(Param::Bytes(self.clone())) } }
 // impl ToParam for bool    { fn to_param(&self) -> Result<Param> { Ok(Param::Int(if *self { 1 } else { 0 })) } }
 impl ToParam for bool    { fn to_param(&self) -> Result<Param> { Ok(Param::Bool((*self).into())) } }
@@ -180,9 +177,16 @@ impl ToParam for i64     { fn to_param(&self) -> Result<Param> { Ok(Param::BigIn
 impl ToParam for f32     { fn to_param(&self) -> Result<Param> { Ok(Param::Real((*self).into())) } }
 impl ToParam for f64     { fn to_param(&self) -> Result<Param> { Ok(Param::Double(*self)) } }
 impl ToParam for String  { fn to_param(&self) -> Result<Param> { Ok(Param::Text(self.clone())) } }
+impl ToParam for str     { fn to_param(&self) -> Result<Param> { Ok(Param::Text(self.to_string())) } }
 impl ToParam for chrono::naive::NaiveDate { fn to_param(&self) -> Result<Param> { Ok(Param::NaiveDate(self.clone())) } }
 impl ToParam for chrono::naive::NaiveDateTime { fn to_param(&self) -> Result<Param> { Ok(Param::NaiveDateTime(self.clone())) } }
 
+impl<T> ToParam for &T
+where T: ToParam + ?Sized
+{
+  fn to_param(&self) -> Result<Param> { (*self).to_param() }
+}
+
 impl<T> ToParam for Option<T>
 where T: ToParam
 {
@@ -0,0 +1,64 @@
+use super::*;
+
+/// Trait to be implemented to retrieve rows affected by insert, update and delete statements. Returns the
+/// `RETURNING` clause appended to these statements.
+pub trait ReturningFlavoredStatement {
+  fn returning_stmt<C, R>(&self, conn: &C) -> Result<String>
+  where C: Connection<R>,
+        R: Row;
+}
+
+/// Trait to be implemented to retrieve rows affected by insert, update and delete statements. Returns the
+/// `RETURNING` clause appended to these statements.
+pub trait ReturningStatement {
+  fn returning_stmt(&self) -> Result<String>;
+}
+
+impl<T> ReturningFlavoredStatement for T
+where T: ReturningStatement
+{
+  fn returning_stmt<C, R>(&self, _conn: &C) -> Result<String>
+  where C: Connection<R>,
+        R: Row,
+  {
+    ReturningStatement::returning_stmt(self)
+  }
+}
+
+/// Insert returning the object stored in database. `RETURNING` is supported by SQLite (3.35+) and PostgreSQL.
+/// Returns `Error::ReturningNotSupported` for MySQL.
+pub trait InsertReturning<C, R, T>
+where C: Connection<R>,
+      R: Row,
+{
+  /// Insert the object and return it as stored in database, ie including values generated by the database
+  fn insert_returning(&self, conn: &mut C, object: &T) -> Result<T>;
+}
+
+/// Update returning the objects updated. `RETURNING` is supported by SQLite (3.35+) and PostgreSQL.
+/// Returns `Error::ReturningNotSupported` for MySQL.
+pub trait UpdateReturning<C, R, T>
+where C: Connection<R>,
+      R: Row,
+{
+  /// Update all items and return them as stored in database following the update
+  fn update_returning(&self, conn: &mut C, object: &T) -> Result<Vec<T>>;
+
+  /// Update items matching the filtering criteria and return them as stored in database following the update
+  fn update_with_filter_returning<F>(&self, conn: &mut C, filter: &F, object: &T) -> Result<Vec<T>>
+  where F: FlavoredFilter;
+}
+
+/// Delete returning the objects deleted. `RETURNING` is supported by SQLite (3.35+) and PostgreSQL.
+/// Returns `Error::ReturningNotSupported` for MySQL.
+pub trait DeleteReturning<C, R, T>
+where C: Connection<R>,
+      R: Row,
+{
+  /// Delete all items and return them
+  fn delete_returning(&self, conn: &mut C) -> Result<Vec<T>>;
+
+  /// Delete items matching the filtering criteria and return them
+  fn delete_with_filter_returning<F>(&self, conn: &mut C, filter: &F) -> Result<Vec<T>>
+  where F: FlavoredFilter;
+}
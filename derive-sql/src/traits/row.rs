@@ -30,6 +30,9 @@ impl<'a> postgres::types::FromSql<'a> for Value {
       true if name.eq(Type::INT8.name()) => Ok(Value::Integer(<i64 as postgres::types::FromSql>::from_sql(ty, raw)?.into())),
       true if name.eq(Type::TEXT.name()) => Ok(Value::Text(<String as postgres::types::FromSql>::from_sql(ty, raw)?.into())),
       true if name.eq(Type::BYTEA.name()) => Ok(Value::Blob(<Vec<u8> as postgres::types::FromSql>::from_sql(ty, raw)?.into())),
+      true if name.eq(Type::TIMESTAMP.name()) => Ok(Value::Text(<chrono::naive::NaiveDateTime as postgres::types::FromSql>::from_sql(ty, raw)?.format("%Y-%m-%d %H:%M:%S%.f").to_string())),
+#[cfg(feature = "json")]
+      true if name.eq(Type::JSON.name()) || name.eq(Type::JSONB.name()) => Ok(Value::Text(<serde_json::Value as postgres::types::FromSql>::from_sql(ty, raw)?.to_string())),
       _ => Err(Error::PostgreSQLInvalidConversion(name.to_string()).into()),
     }
   }
@@ -46,6 +49,9 @@ impl<'a> postgres::types::FromSql<'a> for Value {
       true if name.eq(Type::INT8.name()) => true,
       true if name.eq(Type::TEXT.name()) => true,
       true if name.eq(Type::BYTEA.name()) => true,
+      true if name.eq(Type::TIMESTAMP.name()) => true,
+#[cfg(feature = "json")]
+      true if name.eq(Type::JSON.name()) || name.eq(Type::JSONB.name()) => true,
       _ => false,
     }
   }
@@ -249,6 +255,7 @@ impl TryFromValue for chrono::naive::NaiveDate {
 impl TryFromValue for chrono::naive::NaiveDateTime {
   fn try_from(v: Value) -> Result<Self> {
     match v {
+      Value::Text(v)      => Ok(chrono::naive::NaiveDateTime::parse_from_str(v.as_str(), "%Y-%m-%d %H:%M:%S%.f")?),
 #[cfg(feature = "mysql")]
       Value::MysqlValue(v) => Ok(::mysql::from_value_opt(v)?),
       _ => Err(Error::InvalidTypeForFrom("NaiveDateTime".to_string(), format!("{v:?}"))),
@@ -33,6 +33,17 @@ pub trait SelectFlavoredStatement {
     statement_with_conn_filter_order_limit_offset_options(self.select_stmt(conn)?,
       conn, filter, order, limit, offset)
   }
+
+  /// SQL statement and parameters to retrieve the list of items stored with specifying optionals filter, limit and offset.
+  /// Filter values are returned as parameters bound to the statement placeholders
+  fn select_with_filter_order_limit_offset_options_params_stmt<C, R, F, O>(&self, conn: &C, filter: Option<&F>, order: Option<&O>, limit: Option<usize>, offset: Option<usize>) -> Result<(String, Vec<Param>)> 
+  where C: Connection<R>, R: Row, F: traits::FlavoredFilter, O: traits::FlavoredOrder,
+  {
+    let mut params = Vec::new();
+    let statement = statement_with_conn_params_filter_order_limit_offset_options(self.select_stmt(conn)?,
+      conn, &mut params, filter, order, limit, offset)?;
+    Ok((statement, params))
+  }
 }
 
 pub trait SelectStatement
@@ -98,5 +109,23 @@ where C: Connection<R>,
   /// Retrieve the list of items of the type `T` stored in database
   fn select_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize) -> Result<Vec<T>>
   where F: traits::FlavoredFilter, O: traits::FlavoredOrder;
+
+  /// Retrieve the items of the type `T` stored in database as an iterator provided to `f`. Items are
+  /// retrieved from the database as the iterator is consumed
+  fn select_iter<G, U>(&self, conn: &mut C, f: G) -> Result<U>
+  where G: FnOnce(QueryIterator<'_, T>) -> Result<U>;
+
+  /// Retrieve the items of the type `T` stored in database matching the filtering criteria as an iterator provided to `f`
+  fn select_with_filter_iter<F, G, U>(&self, conn: &mut C, filter: &F, f: G) -> Result<U>
+  where F: traits::FlavoredFilter, G: FnOnce(QueryIterator<'_, T>) -> Result<U>;
 }
 
+pub trait SelectByKeys<C, R, T>
+where C: Connection<R>,
+      R: Row,
+{
+  /// Retrieve the items of the type `T` whose `column` value is one of the given keys. The keys are bound as parameters,
+  /// using one statement per batch of `Flavor::max_params` keys
+  fn select_by_keys<K>(&self, conn: &mut C, column: &str, keys: &[K]) -> Result<Vec<T>>
+  where K: ToParam;
+}
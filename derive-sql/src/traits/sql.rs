@@ -6,27 +6,26 @@ where S: update::UpdateFlavoredStatement,
       R: Row,
       T: params::Params + row::TryFromRefRow<R>,
 {
-  fn update(&self, conn: &mut C, object: &T) -> Result<()> {
-    conn.execute_with_params(self.update_stmt(conn)?, object)?;
-    Ok(())
+  fn update(&self, conn: &mut C, object: &T) -> Result<ExecuteResult> {
+    conn.execute_with_params(self.update_stmt(conn)?, &object.as_vec_update_params()?)
   }
 
-  fn update_with_filter<F>(&self, conn: &mut C, filter: &F, object: &T) -> Result<()>
+  fn update_with_filter<F>(&self, conn: &mut C, filter: &F, object: &T) -> Result<ExecuteResult>
   where F: traits::FlavoredFilter
   {
-    conn.execute_with_params(self.update_with_filter_stmt(conn, filter)?, object)?;
-    Ok(())
+    let (statement, params) = self.update_with_filter_order_limit_offset_options_params_stmt::<_, _, _, structs::order::None, _>(conn, object, Some(filter), None, None, None)?;
+    conn.execute_with_params(statement, &params)
   }
 
-  fn update_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize, object: &T) -> Result<()>
+  fn update_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize, object: &T) -> Result<ExecuteResult>
   where F: traits::FlavoredFilter, O: FlavoredOrder,
   {
     match conn.flavor() {
       Flavor::SQLite
       | Flavor::MySQL => {
         log::warn!("Update with limit and offset will be deprecated");
-        conn.execute_with_params(self.update_with_filter_order_limit_offset_stmt(conn, filter, order, limit, offset)?, object)?;
-        Ok(())
+        let (statement, params) = self.update_with_filter_order_limit_offset_options_params_stmt(conn, object, Some(filter), Some(order), Some(limit), Some(offset))?;
+        conn.execute_with_params(statement, &params)
       }
       Flavor::PostgreSQL => Err(Error::UpdateWithLimitOffsetNotSupported),
     }
@@ -38,23 +37,22 @@ where S: delete::DeleteFlavoredStatement,
       C: Connection<R>,
       R: Row,
 {
-  fn delete(&self, conn: &mut C) -> Result<()> {
-    conn.query_drop(self.delete_stmt(conn)?)?;
-    Ok(())
+  fn delete(&self, conn: &mut C) -> Result<ExecuteResult> {
+    conn.execute_with_params(self.delete_stmt(conn)?, &())
   }
 
-  fn delete_with_filter<F>(&self, conn: &mut C, filter: &F) -> Result<()> 
+  fn delete_with_filter<F>(&self, conn: &mut C, filter: &F) -> Result<ExecuteResult> 
   where F: traits::FlavoredFilter
   {
-    conn.query_drop(self.delete_with_filter_stmt(conn, filter)?)?;
-    Ok(())
+    let (statement, params) = self.delete_with_filter_order_limit_offset_options_params_stmt::<_, _, _, structs::order::None>(conn, Some(filter), None, None, None)?;
+    conn.execute_with_params(statement, &params)
   }
 
-  fn delete_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize) -> Result<()> 
+  fn delete_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize) -> Result<ExecuteResult> 
   where F: traits::FlavoredFilter, O: FlavoredOrder,
   {
-    conn.query_drop(self.delete_with_filter_order_limit_offset_stmt(conn, filter, order, limit, offset)?)?;
-    Ok(())
+    let (statement, params) = self.delete_with_filter_order_limit_offset_options_params_stmt(conn, Some(filter), Some(order), Some(limit), Some(offset))?;
+    conn.execute_with_params(statement, &params)
   }
 }
 
@@ -64,9 +62,8 @@ where S: insert::InsertFlavoredStatement,
       C: Connection<R>,
       R: Row,
 {
-  fn insert(&self, conn: &mut C, object: &T) -> Result<()> {
-    conn.execute_with_params(self.insert_stmt(conn)?, object)?;
-    Ok(())
+  fn insert(&self, conn: &mut C, object: &T) -> Result<ExecuteResult> {
+    conn.execute_with_params(self.insert_stmt(conn)?, &object.as_vec_insert_params()?)
   }
 }
 
@@ -76,11 +73,26 @@ where S: insert::InsertFlavoredStatement,
       C: Connection<R>,
       R: Row,
 {
-  fn insert_multiple<I>(&self, conn: &mut C, objects: I) -> Result<()> 
+  fn insert_multiple<I>(&self, conn: &mut C, objects: I) -> Result<ExecuteResult> 
   where I: core::iter::IntoIterator<Item = &'a T>
   {
-    conn.execute_with_params_iterator(self.insert_stmt(conn)?, objects)?;
-    Ok(())
+    let params = objects.into_iter().map(|o| o.as_vec_insert_params()).collect::<Result<Vec<Vec<Param>>>>()?;
+    conn.execute_with_params_iterator(self.insert_stmt(conn)?, params.iter())
+  }
+}
+
+impl<C, R, T, S> upsert::Upsert<C, R, T> for S
+where S: upsert::UpsertFlavoredStatement,
+      T: params::Params,
+      C: Connection<R>,
+      R: Row,
+{
+  fn upsert(&self, conn: &mut C, object: &T) -> Result<ExecuteResult> {
+    conn.execute_with_params(self.upsert_stmt(conn)?, &object.as_vec_insert_params()?)
+  }
+
+  fn insert_or_ignore(&self, conn: &mut C, object: &T) -> Result<ExecuteResult> {
+    conn.execute_with_params(self.insert_or_ignore_stmt(conn)?, &object.as_vec_insert_params()?)
   }
 }
 
@@ -97,19 +109,56 @@ where S: select::SelectFlavoredStatement,
   fn select_with_filter<F>(&self, conn: &mut C, filter: &F) -> Result<Vec<T>>
   where F: traits::FlavoredFilter
   {
-    conn.query_try_as_object(self.select_with_filter_stmt(conn, filter)?)
+    let (statement, params) = self.select_with_filter_order_limit_offset_options_params_stmt::<_, _, _, structs::order::None>(conn, Some(filter), None, None, None)?;
+    conn.query_with_params_try_as_object(statement, &params)
   }
 
   fn select_with_filter_order<F, O>(&self, conn: &mut C, filter: &F, order: &O) -> Result<Vec<T>>
   where F: traits::FlavoredFilter, O: traits::FlavoredOrder,
   {
-    conn.query_try_as_object(self.select_with_filter_order_stmt(conn, filter, order)?)
+    let (statement, params) = self.select_with_filter_order_limit_offset_options_params_stmt(conn, Some(filter), Some(order), None, None)?;
+    conn.query_with_params_try_as_object(statement, &params)
   }
 
   fn select_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize) -> Result<Vec<T>> 
   where F: traits::FlavoredFilter, O: traits::FlavoredOrder,
   {
-    conn.query_try_as_object(self.select_with_filter_order_limit_offset_stmt(conn, filter, order, limit, offset)?)
+    let (statement, params) = self.select_with_filter_order_limit_offset_options_params_stmt(conn, Some(filter), Some(order), Some(limit), Some(offset))?;
+    conn.query_with_params_try_as_object(statement, &params)
+  }
+
+  fn select_iter<G, U>(&self, conn: &mut C, f: G) -> Result<U>
+  where G: FnOnce(QueryIterator<'_, T>) -> Result<U>,
+  {
+    conn.query_iter_try_as_object(self.select_stmt(conn)?, f)
+  }
+
+  fn select_with_filter_iter<F, G, U>(&self, conn: &mut C, filter: &F, f: G) -> Result<U>
+  where F: traits::FlavoredFilter, G: FnOnce(QueryIterator<'_, T>) -> Result<U>,
+  {
+    let (statement, params) = self.select_with_filter_order_limit_offset_options_params_stmt::<_, _, _, structs::order::None>(conn, Some(filter), None, None, None)?;
+    conn.query_with_params_iter(statement, &params, |rows| f(Box::new(rows.map(|r| T::try_from(&r?)))))
+  }
+}
+
+impl<C, R, T, S> select::SelectByKeys<C, R, T> for S
+where S: select::SelectFlavoredStatement,
+      T: row::TryFromRefRow<R>,
+      C: Connection<R>,
+      R: Row,
+{
+  fn select_by_keys<K>(&self, conn: &mut C, column: &str, keys: &[K]) -> Result<Vec<T>>
+  where K: ToParam,
+  {
+    let flavor = conn.flavor();
+    let mut items = Vec::new();
+    for keys in keys.chunks(flavor.max_params()) {
+      let values = (0..keys.len()).map(|i| flavor.value(i)).collect::<Result<Vec<String>>>()?;
+      let statement = format!("{} WHERE {} IN ( {} )", self.select_stmt(conn)?, flavor.column(column)?, values.join(", "));
+      let params = keys.iter().map(|k| k.to_param()).collect::<Result<Vec<Param>>>()?;
+      items.append(&mut conn.query_with_params_try_as_object(statement, &params)?);
+    }
+    Ok(items)
   }
 }
 
@@ -130,3 +179,56 @@ where S: table::TableFlavoredStatement,
     conn.query_drop(self.drop_stmt(conn)?)
   }
 }
+
+impl<C, R, T, S> returning::InsertReturning<C, R, T> for S
+where S: insert::InsertFlavoredStatement + returning::ReturningFlavoredStatement,
+      T: params::Params + row::TryFromRefRow<R>,
+      C: Connection<R>,
+      R: Row,
+{
+  fn insert_returning(&self, conn: &mut C, object: &T) -> Result<T> {
+    let statement = statement_with_conn_returning(self.insert_stmt(conn)?, conn, self)?;
+    conn.query_first_with_params_try_as_object(statement, &object.as_vec_insert_params()?)?
+    .ok_or(Error::InsertionFail)
+  }
+}
+
+impl<C, R, T, S> returning::UpdateReturning<C, R, T> for S
+where S: update::UpdateFlavoredStatement + returning::ReturningFlavoredStatement,
+      T: params::Params + row::TryFromRefRow<R>,
+      C: Connection<R>,
+      R: Row,
+{
+  fn update_returning(&self, conn: &mut C, object: &T) -> Result<Vec<T>> {
+    let statement = statement_with_conn_returning(self.update_stmt(conn)?, conn, self)?;
+    conn.query_with_params_try_as_object(statement, &object.as_vec_update_params()?)
+  }
+
+  fn update_with_filter_returning<F>(&self, conn: &mut C, filter: &F, object: &T) -> Result<Vec<T>>
+  where F: traits::FlavoredFilter
+  {
+    let (statement, params) = self.update_with_filter_order_limit_offset_options_params_stmt::<_, _, _, structs::order::None, _>(conn, object, Some(filter), None, None, None)?;
+    let statement = statement_with_conn_returning(statement, conn, self)?;
+    conn.query_with_params_try_as_object(statement, &params)
+  }
+}
+
+impl<C, R, T, S> returning::DeleteReturning<C, R, T> for S
+where S: delete::DeleteFlavoredStatement + returning::ReturningFlavoredStatement,
+      T: row::TryFromRefRow<R>,
+      C: Connection<R>,
+      R: Row,
+{
+  fn delete_returning(&self, conn: &mut C) -> Result<Vec<T>> {
+    let statement = statement_with_conn_returning(self.delete_stmt(conn)?, conn, self)?;
+    conn.query_try_as_object(statement)
+  }
+
+  fn delete_with_filter_returning<F>(&self, conn: &mut C, filter: &F) -> Result<Vec<T>>
+  where F: traits::FlavoredFilter
+  {
+    let (statement, params) = self.delete_with_filter_order_limit_offset_options_params_stmt::<_, _, _, structs::order::None>(conn, Some(filter), None, None, None)?;
+    let statement = statement_with_conn_returning(statement, conn, self)?;
+    conn.query_with_params_try_as_object(statement, &params)
+  }
+}
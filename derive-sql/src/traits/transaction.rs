@@ -0,0 +1,194 @@
+//! Transaction guard rolling back the transaction on drop unless committed
+use super::*;
+
+/// Guard over a transaction - or over a savepoint when nested - created using `Connection::begin_transaction`.
+/// Statements are issued on the guard as it implements `Connection`. The transaction is rolled back when
+/// the guard is dropped without `commit` being called.
+///
+/// ```rust
+/// # #[cfg(feature = "sqlite")]
+/// # fn wrapper() {
+/// use derive_sql::traits::Connection;
+///
+/// let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+/// conn.query_drop("CREATE TABLE example_tx (id INTEGER)").unwrap();
+///
+/// let mut tx = conn.begin_transaction().unwrap();
+/// tx.execute_with_params("INSERT INTO example_tx (id) VALUES (?)", &1i64).unwrap();
+/// {
+///   let mut sp = tx.begin_transaction().unwrap();
+///   sp.execute_with_params("INSERT INTO example_tx (id) VALUES (?)", &2i64).unwrap();
+///   // `sp` is dropped without commit: the second insert is rolled back
+/// }
+/// tx.commit().unwrap();
+///
+/// let ids: Vec<i64> = conn.query_try_as_object("SELECT id FROM example_tx").unwrap();
+/// assert!(ids.len() == 1);
+/// # }
+/// ```
+pub struct Transaction<'a, C, R>
+where C: Connection<R>,
+      R: Row,
+{
+  conn: &'a mut C,
+  savepoint: Option<String>,
+  depth: usize,
+  active: bool,
+  phantom_r: std::marker::PhantomData<R>,
+}
+
+impl<'a, C, R> Transaction<'a, C, R>
+where C: Connection<R>,
+      R: Row,
+{
+  /// Start a transaction on the connection
+  pub fn from_connection(conn: &'a mut C) -> Result<Transaction<'a, C, R>> {
+    conn.begin()?;
+    Ok(Transaction { conn, savepoint: None, depth: 0, active: true, phantom_r: std::marker::PhantomData, })
+  }
+
+  /// Create a savepoint on a connection on which a transaction is already started
+  fn with_savepoint(conn: &'a mut C, depth: usize) -> Result<Transaction<'a, C, R>> {
+    let name = format!("derive_sql_savepoint_{depth}");
+    conn.savepoint(name.as_str())?;
+    Ok(Transaction { conn, savepoint: Some(name), depth, active: true, phantom_r: std::marker::PhantomData, })
+  }
+
+  /// Commit the transaction - or release the savepoint when nested
+  pub fn commit(mut self) -> Result<()> {
+    self.active = false;
+    match &self.savepoint {
+      Some(name) => self.conn.release_savepoint(name.as_str()),
+      None       => self.conn.commit(),
+    }
+  }
+
+  /// Rollback the transaction - or rollback to the savepoint when nested
+  pub fn rollback(mut self) -> Result<()> {
+    self.active = false;
+    self.rollback_impl()
+  }
+
+  fn rollback_impl(&mut self) -> Result<()> {
+    match &self.savepoint {
+      Some(name) => {
+        self.conn.rollback_to_savepoint(name.as_str())?;
+        self.conn.release_savepoint(name.as_str())
+      },
+      None => self.conn.rollback(),
+    }
+  }
+}
+
+impl<'a, C, R> std::ops::Drop for Transaction<'a, C, R>
+where C: Connection<R>,
+      R: Row,
+{
+  fn drop(&mut self) {
+    if self.active {
+      if let Err(e) = self.rollback_impl() {
+        ::log::warn!("Rollback of transaction on drop failed with error: {e}");
+      }
+    }
+  }
+}
+
+impl<'a, C, R> Connection<R> for Transaction<'a, C, R>
+where C: Connection<R>,
+      R: Row,
+{
+  fn flavor(&self) -> Flavor { self.conn.flavor() }
+
+  fn execute_with_params<S, P>(&mut self, query: S, params: &P) -> Result<ExecuteResult>
+  where S: std::convert::AsRef<str>,
+        P: Params,
+  {
+    self.conn.execute_with_params(query, params)
+  }
+
+  /// Execute the statement for each item within a nested transaction, ie a savepoint, such that the statements
+  /// are all rolled back on failure while leaving the guard transaction usable.
+  fn execute_with_params_iterator<'b, S, I, P>(&mut self, query: S, params_iter: I) -> Result<ExecuteResult>
+  where S: std::convert::AsRef<str>,
+        P: Params + 'b,
+        I: core::iter::IntoIterator<Item = &'b P>
+  {
+    let sp = self.begin_transaction()?;
+    let mut r = ExecuteResult::default();
+    for params in params_iter { r = r.and(sp.conn.execute_with_params(query.as_ref(), params)?); }
+    sp.commit()?;
+    Ok(r)
+  }
+
+  fn query<S>(&mut self, query: S) -> Result<Vec<R>>
+  where S: std::convert::AsRef<str>,
+  {
+    self.conn.query(query)
+  }
+
+  fn query_with_params<S, P>(&mut self, query: S, params: &P) -> Result<Vec<R>>
+  where S: std::convert::AsRef<str>,
+        P: Params,
+  {
+    self.conn.query_with_params(query, params)
+  }
+
+  fn query_with_params_iter<S, P, F, U>(&mut self, query: S, params: &P, f: F) -> Result<U>
+  where S: std::convert::AsRef<str>,
+        P: Params,
+        F: FnOnce(QueryIterator<'_, R>) -> Result<U>,
+  {
+    self.conn.query_with_params_iter(query, params, f)
+  }
+
+  /// Not available on a guard: use `begin_transaction` to create a nested transaction
+  fn begin(&mut self) -> Result<()> { Err(Error::TransactionInProgress) }
+
+  /// Not available on a guard: use `Transaction::commit`
+  fn commit(&mut self) -> Result<()> { Err(Error::TransactionInProgress) }
+
+  /// Not available on a guard: use `Transaction::rollback`
+  fn rollback(&mut self) -> Result<()> { Err(Error::TransactionInProgress) }
+
+  fn savepoint(&mut self, name: &str) -> Result<()> { self.conn.savepoint(name) }
+
+  fn release_savepoint(&mut self, name: &str) -> Result<()> { self.conn.release_savepoint(name) }
+
+  fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> { self.conn.rollback_to_savepoint(name) }
+
+  /// Create a nested transaction using a savepoint
+  fn begin_transaction(&mut self) -> Result<Transaction<'_, Self, R>> {
+    let depth = self.depth + 1;
+    Transaction::with_savepoint(self, depth)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_runs_statements_of_a_guard_in_a_savepoint() -> Result<()> {
+    let mut conn = proxy::Mock::from_flavor(Flavor::PostgreSQL)
+      .with_error("unknown_table", "relation does not exist");
+
+    let mut tx = conn.begin_transaction()?;
+    tx.execute_with_params_iterator("INSERT INTO person (id) VALUES ($1)", [1i64, 2i64].iter())?;
+    assert!(tx.execute_with_params_iterator("INSERT INTO unknown_table (id) VALUES ($1)", [3i64].iter()).is_err());
+    tx.commit()?;
+
+    assert!(conn.sql() == vec![
+      "BEGIN",
+      "SAVEPOINT derive_sql_savepoint_1",
+      "INSERT INTO person (id) VALUES ($1)",
+      "INSERT INTO person (id) VALUES ($1)",
+      "RELEASE SAVEPOINT derive_sql_savepoint_1",
+      "SAVEPOINT derive_sql_savepoint_1",
+      "INSERT INTO unknown_table (id) VALUES ($1)",
+      "ROLLBACK TO SAVEPOINT derive_sql_savepoint_1",
+      "RELEASE SAVEPOINT derive_sql_savepoint_1",
+      "COMMIT",
+    ]);
+    Ok(())
+  }
+}
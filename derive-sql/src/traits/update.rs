@@ -17,6 +17,17 @@ pub trait UpdateFlavoredStatement {
     statement_with_conn_filter_order_limit_offset_options(self.update_stmt(conn)?,
       conn, Some(filter), Some(order), Some(limit), Some(offset))
   }
+
+  /// Update statement and parameters with optionals filter, order, limit and offset. The parameters are the
+  /// object parameters followed by the filter values
+  fn update_with_filter_order_limit_offset_options_params_stmt<C, R, F, O, T>(&self, conn: &C, object: &T, filter: Option<&F>, order: Option<&O>, limit: Option<usize>, offset: Option<usize>) -> Result<(String, Vec<Param>)>
+  where C: Connection<R>, R: Row, F: FlavoredFilter, O: FlavoredOrder, T: Params,
+  {
+    let mut params = object.as_vec_update_params()?;
+    let statement = statement_with_conn_params_filter_order_limit_offset_options(self.update_stmt(conn)?,
+      conn, &mut params, filter, order, limit, offset)?;
+    Ok((statement, params))
+  }
 }
 
 pub trait UpdateStatement {
@@ -51,12 +62,12 @@ pub trait Update<C, R, T>
 where C: Connection<R>,
       R: Row,
 {
-  fn update(&self, conn: &mut C, object: &T) -> Result<()>;
+  fn update(&self, conn: &mut C, object: &T) -> Result<ExecuteResult>;
 
-  fn update_with_filter<F>(&self, conn: &mut C, filter: &F, object: &T) -> Result<()>
+  fn update_with_filter<F>(&self, conn: &mut C, filter: &F, object: &T) -> Result<ExecuteResult>
   where F: traits::FlavoredFilter;
 
-  fn update_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize, object: &T) -> Result<()>
+  fn update_with_filter_order_limit_offset<F, O>(&self, conn: &mut C, filter: &F, order: &O, limit: usize, offset: usize, object: &T) -> Result<ExecuteResult>
   where F: traits::FlavoredFilter, O: FlavoredOrder;
 
 }
@@ -0,0 +1,48 @@
+use super::*;
+
+pub trait UpsertFlavoredStatement {
+  /// Insert statement updating the existing row when the item conflicts with a primary key or unique constraint
+  fn upsert_stmt<C, R>(&self, conn: &C) -> Result<String>
+  where C: Connection<R>,
+        R: Row;
+
+  /// Insert statement leaving the existing row untouched when the item conflicts with a primary key or unique constraint
+  fn insert_or_ignore_stmt<C, R>(&self, conn: &C) -> Result<String>
+  where C: Connection<R>,
+        R: Row;
+}
+
+pub trait UpsertStatement {
+  fn upsert_stmt(&self) -> Result<String>;
+  fn insert_or_ignore_stmt(&self) -> Result<String>;
+}
+
+impl<T> UpsertFlavoredStatement for T
+where T: UpsertStatement
+{
+  fn upsert_stmt<C, R>(&self, _conn: &C) -> Result<String>
+  where C: Connection<R>,
+        R: Row,
+  {
+    UpsertStatement::upsert_stmt(self)
+  }
+
+  fn insert_or_ignore_stmt<C, R>(&self, _conn: &C) -> Result<String>
+  where C: Connection<R>,
+        R: Row,
+  {
+    UpsertStatement::insert_or_ignore_stmt(self)
+  }
+}
+
+pub trait Upsert<C, R, T>
+where C: Connection<R>,
+      R: Row,
+{
+  /// Insert the object or update the existing row on key conflict in a single statement. Note that MySQL reports
+  /// 2 rows affected when an existing row is updated.
+  fn upsert(&self, conn: &mut C, object: &T) -> Result<ExecuteResult>;
+
+  /// Insert the object unless it conflicts with an existing row, in which case the existing row is left untouched
+  fn insert_or_ignore(&self, conn: &mut C, object: &T) -> Result<ExecuteResult>;
+}
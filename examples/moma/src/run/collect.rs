@@ -46,6 +46,10 @@ where C: derive_sql::traits::Connection<R>, R: derive_sql::traits::Row,
 {
   use derive_sql::traits::{Table, InsertMultiple, SelectV2};
 
+  // Collect all information or none: the transaction is rolled back if any step fails
+  let mut tx = conn.begin_transaction()?;
+  let conn = &mut tx;
+
   { // Artists information
     log::info!("Collect artist information");
     let sql = table::artist::SqlArtist::default();
@@ -81,6 +85,7 @@ where C: derive_sql::traits::Connection<R>, R: derive_sql::traits::Row,
     log::info!("{count} artwork attributions added");
   }
 
+  tx.commit()?;
   Ok(())
 }
 
@@ -127,4 +132,26 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn it_collects_nothing_when_artworks_fail() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+
+    let r = collect_impl(&mut conn,
+      || Ok(vec![
+        table::artist::Artist::make(1, "artist_1", "A. One", "A", 1, 2),
+      ]),
+      || Err(Error::NotImplemented),
+    );
+    assert!(r.is_err());
+
+    // The artist table creation and insertion are rolled back
+    let r: rusqlite::Result<u32> = conn.query_row(
+      format!("SELECT COUNT(*) FROM {table}", table=table::artist::SqlArtist::TABLE_NAME).as_str(),
+      [],
+      |row| row.get(0));
+    assert!(r.is_err());
+
+    Ok(())
+  }
 }
@@ -13,25 +13,75 @@ struct FieldAttrs {
   on_update: Option<syn::PatPath>,
 }
 
+#[derive(Attribute)]
+#[attribute(ident = derive_sql)]
+struct SqlFieldAttrs {
+  column: Option<String>,
+  skip: bool,
+  json: bool,
+  is_primary_key: bool,
+  is_unique: bool,
+  auto_increment: bool,
+  on_insert: Option<syn::PatPath>,
+  on_update: Option<syn::PatPath>,
+  created_at: bool,
+  updated_at: bool,
+  references: Option<String>,
+  on_delete: Option<String>,
+}
+
 pub struct Fields<'a> {
   ident: &'a syn::Ident,
+  ty: &'a syn::Type,
   sql_type: SqlType,
   attrs: FieldAttrs,
+  sql_attrs: SqlFieldAttrs,
   raw_type: String,
+  references: Option<(String, String)>,
+  on_delete: Option<&'static str>,
 }
 
 impl<'a> std::convert::TryFrom<&'a syn::Field> for Fields<'a> {
   type Error = Box<dyn std::error::Error>;
   fn try_from(f: &'a syn::Field) -> Result<Self, Self::Error> {
-    let sql_type: SqlType = f.into();
-    let raw_type: String = extract_type(&f.ty).ok_or(format!("Unable to retrieve raw type for {:#?}", &f.ty))?;
-    if matches!(sql_type, SqlType::Unsupported) { return Err("Type is not supported".into()); }
+    let sql_attrs = SqlFieldAttrs::from_attributes(&f.attrs)?;
+    let sql_type: SqlType = if sql_attrs.json { SqlType::json_from_type(&f.ty) } else { f.into() };
+    // Skipped fields are not persisted and JSON fields are serialized: their type does not need to be supported
+    let raw_type: String = match sql_attrs.skip || sql_attrs.json {
+      true  => extract_type(&f.ty).unwrap_or_default(),
+      false => extract_type(&f.ty).ok_or(format!("Unable to retrieve raw type for {:#?}", &f.ty))?,
+    };
+    if ! sql_attrs.skip && matches!(sql_type, SqlType::Unsupported) { return Err("Type is not supported".into()); }
+    // `references = "table(column)"`
+    let references = sql_attrs.references.as_ref()
+      .map(|r| {
+        r.strip_suffix(')').and_then(|r| r.split_once('('))
+        .map(|(table, column)| (table.trim().to_string(), column.trim().to_string()))
+        .filter(|(table, column)| ! table.is_empty() && ! column.is_empty())
+        .ok_or(format!("Invalid `references = \"{r}\"`: expected `references = \"table(column)\"`"))
+      })
+      .transpose()?;
+    let on_delete = sql_attrs.on_delete.as_ref()
+      .map(|a| match a.to_lowercase().as_str() {
+        "cascade"     => Ok("CASCADE"),
+        "restrict"    => Ok("RESTRICT"),
+        "set null"    => Ok("SET NULL"),
+        "set default" => Ok("SET DEFAULT"),
+        "no action"   => Ok("NO ACTION"),
+        _ => Err(format!("Invalid `on_delete = \"{a}\"`: expected `cascade`, `restrict`, `set null`, `set default` or `no action`")),
+      })
+      .transpose()?;
+    if on_delete.is_some() && references.is_none() { return Err("`on_delete` requires `references`".into()); }
     Ok( 
       Fields {
         ident: f.ident.as_ref().ok_or("Field does not have an ident")?,
+        ty: &f.ty,
         sql_type,
         attrs: FieldAttrs::from_attributes(&f.attrs)?,
+        sql_attrs,
         raw_type,
+        references,
+        on_delete,
       } 
     )
   }
@@ -39,23 +89,39 @@ impl<'a> std::convert::TryFrom<&'a syn::Field> for Fields<'a> {
 
 impl<'a> Fields<'a> {
   pub fn name(&'a self) -> String { format!("{}", self.ident) }
+  /// Name of the column in the table: the name nominated with `column = "..."` or the name of the field
+  pub fn column_name(&self) -> String { self.sql_attrs.column.clone().unwrap_or_else(|| format!("{}", self.ident)) }
+  /// Whether the field is excluded from the table, ie nominated with `skip`
+  pub fn is_skip(&self) -> bool { self.sql_attrs.skip }
   pub fn ident(&'a self) -> &'a syn::Ident { self.ident }
+  pub fn ty(&'a self) -> &'a syn::Type { self.ty }
   pub fn raw_type(&'a self) -> &'a str { self.raw_type.as_str() }
   pub fn sql_type(&'a self) -> &'a SqlType { &self.sql_type }
-  pub fn is_primary_key(&'a self) -> bool { self.attrs.is_primary_key }
-  pub fn is_unique(&self) -> bool { self.attrs.is_unique }
-  pub fn on_insert(&'a self) -> &'a Option<syn::PatPath> { &self.attrs.on_insert }
-  pub fn on_update(&'a self) -> &'a Option<syn::PatPath> { &self.attrs.on_update }
+  pub fn is_nullable(&self) -> bool { self.sql_type.is_nullable() }
+  pub fn is_primary_key(&'a self) -> bool { self.attrs.is_primary_key || self.sql_attrs.is_primary_key }
+  pub fn is_unique(&self) -> bool { self.attrs.is_unique || self.sql_attrs.is_unique }
+  /// Whether the value of the field is generated by the database on insert
+  pub fn is_auto_increment(&self) -> bool { self.sql_attrs.auto_increment }
+  pub fn on_insert(&'a self) -> Option<&'a syn::PatPath> { self.attrs.on_insert.as_ref().or(self.sql_attrs.on_insert.as_ref()) }
+  pub fn on_update(&'a self) -> Option<&'a syn::PatPath> { self.attrs.on_update.as_ref().or(self.sql_attrs.on_update.as_ref()) }
+  /// Table and column referenced by the field, nominated with `references = "table(column)"`
+  pub fn references(&self) -> Option<(&str, &str)> { self.references.as_ref().map(|(t, c)| (t.as_str(), c.as_str())) }
+  /// Referential action on delete of the referenced row, nominated with `on_delete = "..."`
+  pub fn on_delete(&self) -> Option<&'static str> { self.on_delete }
+  /// Whether the field is set to the current time on insert
+  pub fn is_created_at(&self) -> bool { self.sql_attrs.created_at }
+  /// Whether the field is set to the current time on insert and on update
+  pub fn is_updated_at(&self) -> bool { self.sql_attrs.updated_at }
   pub fn as_pub_static_member(&'a self) -> proc_macro2::TokenStream {
     let key: syn::Ident = syn::Ident::new(self.name().to_ascii_uppercase().as_str(), self.ident.span()); 
-    let value = self.name();
+    let value = self.column_name();
     quote::quote! {
       pub const #key : &'static str = #value;
     }
   }
 }
 
-/// Retrieve the final type as a string, such "std::option::Option<f32>" gives "f32"
+/// Retrieve the final type as a string, such "std::option::Option<f32>" gives "f32" and "Option<Vec<u8>>" gives "Vec<u8>"
 fn extract_type(ty: &syn::Type) -> Option<String> {
   match ty {
     syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. } , .. }) => {
@@ -64,7 +130,15 @@ fn extract_type(ty: &syn::Type) -> Option<String> {
             arguments: syn::PathArguments::AngleBracketed( syn::AngleBracketedGenericArguments { args, ..  } )
         }) if ident == "Option" => {
           match args.last() {
-            Some(syn::GenericArgument::Type(syn::Type::Path(syn::TypePath { path, .. }))) => path.get_ident().map(|i| i.to_string()),
+            Some(syn::GenericArgument::Type(ty)) => extract_type(ty),
+            _ => None,
+          }
+        },
+        Some(syn::PathSegment { ident,
+            arguments: syn::PathArguments::AngleBracketed( syn::AngleBracketedGenericArguments { args, ..  } )
+        }) if ident == "Vec" => {
+          match args.last() {
+            Some(syn::GenericArgument::Type(ty)) => extract_type(ty).map(|t| format!("Vec<{t}>")),
             _ => None,
           }
         },
@@ -4,13 +4,25 @@
 #[derive(Debug)]
 pub enum SqlType {
   Integer,
+  OptionInteger,
   Text,
   OptionText,
   Boolean,
+  OptionBoolean,
   Float,
   OptionFloat,
   DateTime,
+  OptionDateTime,
   Date,
+  OptionDate,
+  Blob,
+  OptionBlob,
+  /// Type not known to the derive macro, converted through the `SqlColumnType` trait
+  Custom,
+  OptionCustom,
+  /// Type serialized to a JSON column
+  Json,
+  OptionJson,
   Unsupported,
 }
 
@@ -32,6 +44,8 @@ impl SqlType {
       syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("String") => SqlType::Text,
       syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("i8")     => SqlType::Integer,
       syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("u8")     => SqlType::Integer,
+      syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("i16")    => SqlType::Integer,
+      syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("u16")    => SqlType::Integer,
       syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("i32")    => SqlType::Integer,
       syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("u32")    => SqlType::Integer,
       syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("i64")    => SqlType::Integer,
@@ -45,16 +59,24 @@ impl SqlType {
           Some(syn::PathSegment { ident, ..}) if ident == "DateTime"  => SqlType::DateTime,
           Some(syn::PathSegment { ident, ..}) if ident == "NaiveDate" => SqlType::Date,
           Some(syn::PathSegment { ident, ..}) if ident == "NaiveDateTime" => SqlType::DateTime,
+          Some(syn::PathSegment { ident, 
+            arguments: syn::PathArguments::AngleBracketed( syn::AngleBracketedGenericArguments { args, ..  } )
+          }) if ident == "Vec" => {
+            match args.last() {
+              Some(syn::GenericArgument::Type(syn::Type::Path(syn::TypePath { path, .. }))) if path.is_ident("u8") => SqlType::Blob,
+              _ => SqlType::Unsupported,
+            }
+          },
           Some(syn::PathSegment { ident, 
             arguments: syn::PathArguments::AngleBracketed( syn::AngleBracketedGenericArguments { args, ..  } )
           }) if ident == "Option" => {
             match args.last() {
-              Some(syn::GenericArgument::Type(syn::Type::Path(syn::TypePath { path, .. }))) if path.is_ident("String") => SqlType::OptionText,
-              Some(syn::GenericArgument::Type(syn::Type::Path(syn::TypePath { path, .. }))) if path.is_ident("f32")    => SqlType::OptionFloat,
-              Some(syn::GenericArgument::Type(syn::Type::Path(syn::TypePath { path, .. }))) if path.is_ident("f64")    => SqlType::OptionFloat,
+              Some(syn::GenericArgument::Type(ty)) => SqlType::from_type(ty).into_option(),
               _ => SqlType::Unsupported,
             }
           },
+          Some(syn::PathSegment { ident, .. }) if ident == "Vec" => SqlType::Unsupported,
+          Some(_) => SqlType::Custom,
           _ => SqlType::Unsupported,
         }
       },
@@ -62,17 +84,68 @@ impl SqlType {
     }
   }
 
+  /// Type stored as JSON document: nullable when the type is an `Option`
+  pub fn json_from_type(ty: &syn::Type) -> SqlType {
+    match ty {
+      syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. }) 
+        if segments.last().map(|s| s.ident == "Option").unwrap_or(false) => SqlType::OptionJson,
+      _ => SqlType::Json,
+    }
+  }
+
+  /// Optional counterpart of the type. `Option<Option<T>>` is not supported.
+  fn into_option(self) -> SqlType {
+    match self {
+      SqlType::Integer  => SqlType::OptionInteger,
+      SqlType::Text     => SqlType::OptionText,
+      SqlType::Boolean  => SqlType::OptionBoolean,
+      SqlType::Float    => SqlType::OptionFloat,
+      SqlType::DateTime => SqlType::OptionDateTime,
+      SqlType::Date     => SqlType::OptionDate,
+      SqlType::Blob     => SqlType::OptionBlob,
+      SqlType::Custom   => SqlType::OptionCustom,
+      _ => SqlType::Unsupported,
+    }
+  }
+
+  /// Whether the column accepts `NULL` values, ie the field is an `Option`
+  pub fn is_nullable(&self) -> bool {
+    matches!(self, SqlType::OptionInteger | SqlType::OptionText | SqlType::OptionBoolean 
+      | SqlType::OptionFloat | SqlType::OptionDateTime | SqlType::OptionDate | SqlType::OptionBlob
+      | SqlType::OptionCustom | SqlType::OptionJson)
+  }
+
+  /// Whether the type is converted through the `SqlColumnType` trait
+  pub fn is_custom(&self) -> bool {
+    matches!(self, SqlType::Custom | SqlType::OptionCustom)
+  }
+
+  /// Whether the type is serialized to a JSON column
+  pub fn is_json(&self) -> bool {
+    matches!(self, SqlType::Json | SqlType::OptionJson)
+  }
+
   pub fn to_string(&self) -> &str {
     match self {
-      SqlType::Integer     => "INTEGER",
-      SqlType::Text        => "TEXT",
-      SqlType::OptionText  => "TEXT",
-      SqlType::Boolean     => "BOOL", // "BIT",
-      SqlType::Float       => "FLOAT",
-      SqlType::OptionFloat => "FLOAT",
-      SqlType::DateTime    => "DATETIME",
-      SqlType::Date        => "DATE",
-      SqlType::Unsupported => "", 
+      SqlType::Integer        => "INTEGER",
+      SqlType::OptionInteger  => "INTEGER",
+      SqlType::Text           => "TEXT",
+      SqlType::OptionText     => "TEXT",
+      SqlType::Boolean        => "BOOL", // "BIT",
+      SqlType::OptionBoolean  => "BOOL",
+      SqlType::Float          => "FLOAT",
+      SqlType::OptionFloat    => "FLOAT",
+      SqlType::DateTime       => "DATETIME",
+      SqlType::OptionDateTime => "DATETIME",
+      SqlType::Date           => "DATE",
+      SqlType::OptionDate     => "DATE",
+      SqlType::Blob           => "BLOB",
+      SqlType::OptionBlob     => "BLOB",
+      SqlType::Custom         => "",
+      SqlType::OptionCustom   => "",
+      SqlType::Json           => "TEXT",
+      SqlType::OptionJson     => "TEXT",
+      SqlType::Unsupported    => "", 
     }
   }
 }
@@ -135,6 +208,41 @@ mod test_sql_type {
     assert!(matches!(t, SqlType::DateTime));
     assert!(t.to_string().eq("DATETIME"));
 
+    for (k, sql) in [("Option<u32>", "INTEGER"), ("Option<i64>", "INTEGER"), ("Option<bool>", "BOOL"), ("Option<f64>", "FLOAT"),
+      ("Option<NaiveDate>", "DATE"), ("Option<chrono::NaiveDateTime>", "DATETIME"), ("std::option::Option<String>", "TEXT")] {
+      let t = syn::parse_str::<syn::Type>(k)?;
+      let t = SqlType::from_type(&t);
+      assert!(t.is_nullable());
+      assert!(t.to_string().eq(sql));
+    }
+    assert!(! SqlType::from_type(&syn::parse_str::<syn::Type>("NaiveDate")?).is_nullable());
+    assert!(matches!(SqlType::from_type(&syn::parse_str::<syn::Type>("Option<Option<u32>>")?), SqlType::Unsupported));
+    assert!(matches!(SqlType::from_type(&syn::parse_str::<syn::Type>("Option<Vec<u32>>")?), SqlType::Unsupported));
+
+    let t = syn::parse_str::<syn::Type>("Vec<u8>")?;
+    let t = SqlType::from_type(&t);
+    assert!(matches!(t, SqlType::Blob));
+    assert!(t.to_string().eq("BLOB"));
+
+    let t = syn::parse_str::<syn::Type>("Option<Vec<u8>>")?;
+    let t = SqlType::from_type(&t);
+    assert!(matches!(t, SqlType::OptionBlob));
+    assert!(t.is_nullable());
+
+    for k in ["Email", "domain::UserId", "Option<Email>"] {
+      let t = syn::parse_str::<syn::Type>(k)?;
+      let t = SqlType::from_type(&t);
+      assert!(t.is_custom());
+      assert!(t.is_nullable() == k.starts_with("Option"));
+    }
+
+    for k in ["Vec<String>", "std::collections::HashMap<String, usize>", "Option<Vec<usize>>"] {
+      let t = syn::parse_str::<syn::Type>(k)?;
+      let t = SqlType::json_from_type(&t);
+      assert!(t.is_json());
+      assert!(t.is_nullable() == k.starts_with("Option"));
+    }
+
     Ok(())
   }
 }
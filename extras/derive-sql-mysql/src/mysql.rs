@@ -29,6 +29,9 @@ impl<'a> Mysql<'a> {
     let fields = self.fields_named.named.iter()
       .map(|f| f.try_into().map_err(|e| syn::Error::new(ident.span(), format!("{e}"))))
       .collect::<std::result::Result<Vec<fields::Fields>, syn::Error>>()?;
+    if let Some(f) = fields.iter().find(|f| f.sql_type().is_custom() || f.sql_type().is_json()) {
+      return Err(syn::Error::new(ident.span(), format!("Field `{}` error: Type is not supported", f.name())));
+    }
 
     // Primary key of SQL type TEXT is not supported
     if let Some(name) = fields.iter().fold(None, |r, f| r.or_else(|| if f.is_primary_key() && (matches!(f.sql_type(), SqlType::Text) || matches!(f.sql_type(), SqlType::OptionText)) { Some(f.name()) } else { None })) {
@@ -148,7 +151,7 @@ impl<'a> Mysql<'a> {
       let functions = fields.iter()
         .filter_map(|f| {
           let ident = f.ident();
-          f.on_insert().as_ref().map(|p| quote::quote! { item.#ident = #p(); })
+          f.on_insert().map(|p| quote::quote! { item.#ident = #p(); })
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
       let statement = format!("INSERT INTO {table_name} ({}) VALUES ({})",
@@ -179,7 +182,7 @@ impl<'a> Mysql<'a> {
       let functions = fields.iter()
         .filter_map(|f| {
           let ident = f.ident();
-          f.on_update().as_ref().map(|p| quote::quote! { item.#ident = #p(); })
+          f.on_update().map(|p| quote::quote! { item.#ident = #p(); })
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
       let statement = format!("UPDATE {table_name} SET {}",
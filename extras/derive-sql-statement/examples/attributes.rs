@@ -9,16 +9,29 @@ fn init_logger() {
   .try_init();
 }
 
+#[derive(Debug, PartialEq)]
+#[derive(derive_sql::DeriveSqlEnum)]           // Store the enum as text using the variant name (or `#[derive_sql(repr = "int")]`)
+enum Role {
+  #[derive_sql(rename = "admin")]             // Use the nominated text for the variant (default: Admin)
+  Admin,
+  #[derive_sql(rename = "member")]
+  Member,
+}
+
 #[derive(Debug)]
 #[derive(derive_sql_statement::DeriveSqlStatement)]
 #[derive_sql(ident = PersonSql)]        // Use the nominated name for the MySQL wrapper structure (default: PersonMysql)
 #[derive_sql(table_name = "person_v1")] // Use the nominated name for the MySQL table (default: person)
 struct Person {
   #[derive_sqlite(is_primary_key = true)] // Nominate the primary key (duplicates are not allowed)
+  #[derive_sql(column = "person_key")]    // Use the nominated name for the column (default: key)
   key: u32,
   name: String,
   age: u32,
   active: bool,
+  role: Role,
+  #[derive_sql(skip)]                     // Do not persist the field - set to `Default` when read from the database
+  greeting: Option<String>,
 }
 
 fn main() {
@@ -37,7 +50,7 @@ fn sample<C, R>(conn: &mut C) -> Result<()>
 where C: traits::Connection<R>,
       R: traits::Row,
 {
-  use derive_sql::traits::{Table, SelectV2, Insert, Delete, Update};
+  use derive_sql::traits::{Table, SelectV2, Insert, Delete, Update, Upsert};
   let db = PersonSql::default();
 
   // Drop table if exists
@@ -49,18 +62,32 @@ where C: traits::Connection<R>,
 
   // Insert person into database
   log::info!("Insert person...");
-  let person = Person { key: 1, name: "Jo".to_string(), age: 44, active: true };
+  let person = Person { key: 1, name: "Jo".to_string(), age: 44, active: true, role: Role::Admin, greeting: Some("Hello Jo".to_string()) };
   db.insert(conn, &person)?;
   log::info!("Check person insertion");
   let person: Vec<Person> = db.select(conn)?;
   assert!(person[0].name.eq("Jo"));
+  assert!(person[0].greeting.is_none());
+  assert!(person[0].role == Role::Admin);
+  let person: Vec<Person> = db.select_with_filter(conn, &derive_sql::structs::Field::from(PersonSql::KEY).eq(1))?;
+  assert!(PersonSql::KEY.eq("person_key") && person.len() == 1);
   log::info!("Insert person... ok");
 
   // Inserting another person with the same name (ie primary key) should fail
   log::info!("Insert person with duplicated name [primary key] fails...");
-  assert!(db.insert(conn, &Person { key: 1, name: "Jo".to_string(), age: 32, active: true }).is_err());
+  assert!(db.insert(conn, &Person { key: 1, name: "Jo".to_string(), age: 32, active: true, role: Role::Member, greeting: None }).is_err());
   log::info!("Insert person with duplicated name [primary key] fails... ok");
 
+  // Upsert a person with the same key updates the existing person
+  log::info!("Upsert person with duplicated key [primary key] updates person...");
+  db.upsert(conn, &Person { key: 1, name: "Jo".to_string(), age: 45, active: true, role: Role::Member, greeting: None })?;
+  db.insert_or_ignore(conn, &Person { key: 1, name: "Jo".to_string(), age: 46, active: true, role: Role::Member, greeting: None })?;
+  db.insert_or_ignore(conn, &Person { key: 2, name: "Jack".to_string(), age: 32, active: true, role: Role::Member, greeting: None })?;
+  let person: Vec<Person> = db.select(conn)?;
+  assert!(person.len() == 2);
+  assert!(person[0].age == 45);
+  log::info!("Upsert person with duplicated key [primary key] updates person... ok");
+
   // Delete the table
   log::info!("Delete table");
   db.drop(conn)?;
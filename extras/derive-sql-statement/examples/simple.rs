@@ -16,6 +16,8 @@ struct Person {
   age: usize,
   active: bool,
   nickname: Option<String>,
+  score: Option<u32>,
+  avatar: Option<Vec<u8>>,
 }
 
 fn main() {
@@ -70,16 +72,16 @@ where Conn: traits::Connection<Row>,
 
   // Insert person into database
   log::info!("Insert person...");
-  let person = Person { name: "Jo".to_string(), age: 44, active: true, nickname: None, };
+  let person = Person { name: "Jo".to_string(), age: 44, active: true, nickname: None, score: None, avatar: None, };
   db.insert(conn, &person)?;
   log::info!("Check person insertion...");
   let persons: Vec<Person> = db.select(conn)?;
   assert!(persons[0].name.eq("Jo"));
   log::info!("Insert person... ok");
 
-  let _ = db.insert(conn, &Person { name: "Jack".to_string(),  age: 44, active: true, nickname: None,})?;
-  let _ = db.insert(conn, &Person { name: "Harry".to_string(), age: 27, active: true, nickname: Some("The H".to_string()),})?;
-  let _ = db.insert(conn, &Person { name: "Jack".to_string(),  age: 27, active: false, nickname: None,})?;
+  let _ = db.insert(conn, &Person { name: "Jack".to_string(),  age: 44, active: true, nickname: None, score: None, avatar: None,})?;
+  let _ = db.insert(conn, &Person { name: "Harry".to_string(), age: 27, active: true, nickname: Some("The H".to_string()), score: Some(7), avatar: Some(vec![0x89, 0x50, 0x4e, 0x47]),})?;
+  let _ = db.insert(conn, &Person { name: "Jack".to_string(),  age: 27, active: false, nickname: None, score: None, avatar: None,})?;
 
   // Retrieve list of persons from SQL database
   log::info!("Retrieve list of persons...");
@@ -120,6 +122,8 @@ where Conn: traits::Connection<Row>,
   let persons: Vec<Person> = db.select_with_filter(conn, &Field::from("nickname").is_some())?;
   assert!(persons.len() == 1);
   assert!(persons[0].name.eq("Harry"));
+  assert!(persons[0].score == Some(7));
+  assert!(persons[0].avatar.as_deref() == Some([0x89, 0x50, 0x4e, 0x47].as_slice()));
   let persons: Vec<Person> = db.select_with_filter(conn, &Field::from("nickname").is_none())?;
   assert!(persons.len() == 3);
 
@@ -156,7 +160,7 @@ where Conn: traits::Connection<Row>,
       }
       db.update_with_filter(conn,
         &CustomFilter {},
-        &Person { name: "Jo".to_string(), age: 44, active: true, nickname: None, },
+        &Person { name: "Jo".to_string(), age: 44, active: true, nickname: None, score: None, avatar: None, },
       )?;
     },
 
@@ -167,7 +171,7 @@ where Conn: traits::Connection<Row>,
         &order::None::default(),
         1, // limit
         0, // offset
-        &Person { name: "Jo".to_string(), age: 44, active: true, nickname: None, },
+        &Person { name: "Jo".to_string(), age: 44, active: true, nickname: None, score: None, avatar: None, },
       )?;
     },
   }
@@ -176,6 +180,28 @@ where Conn: traits::Connection<Row>,
   assert!(persons.len() == 1);
   log::info!("Update persons with filter and limit... ok");
 
+  // Retrieve the persons inserted and deleted
+  log::info!("Insert and delete returning persons...");
+  {
+    use derive_sql::traits::{InsertReturning, DeleteReturning};
+    let person = Person { name: "Kim".to_string(), age: 31, active: true, nickname: None, score: None, avatar: None, };
+    match conn.flavor() {
+      derive_sql::traits::Flavor::SQLite
+      | derive_sql::traits::Flavor::PostgreSQL => {
+        let person: Person = db.insert_returning(conn, &person)?;
+        assert!(person.name.eq("Kim"));
+        let persons: Vec<Person> = db.delete_with_filter_returning(conn, &Field::from("name").eq("Kim"))?;
+        assert!(persons.len() == 1);
+        assert!(persons[0].age == 31);
+      },
+      derive_sql::traits::Flavor::MySQL => {
+        let r: derive_sql::Result<Person> = db.insert_returning(conn, &person);
+        assert!(r.is_err());
+      },
+    }
+  }
+  log::info!("Insert and delete returning persons... ok");
+
   // Delete the table
   log::info!("Delete table");
   db.drop(conn)?;
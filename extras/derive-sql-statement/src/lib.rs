@@ -60,11 +60,44 @@
 //! # Container attributes:
 //! - `#[derive_sqlite(ident = ...)]` overwrite the name of the wrapper from `Sql{class}`;
 //! - `#[derive_sqlite(table_name = "...")]` specify the name of the table (default to the container name in lower case);
-//! - `#[derive_sqlite(read_only = true/false)]` specify whether to implement read/write (ie table, select, insert, update, delete, to params conversion and from row conversion)
+//! - `#[derive_sqlite(read_only = true/false)]` specify whether to implement read/write (ie table, select, insert, update, delete, returning, to params conversion and from row conversion)
 //!    or read only statements (ie select and from row conversion)
+//! - `#[derive_sql(primary_key(field_a, field_b))]` nominate the fields forming a composite primary key;
+//! - `#[derive_sql(unique(field_a, field_b))]` nominate fields whose combination is unique in the table. Can be repeated for several constraints.
+//!
+//! ```rust
+//! use derive_sql::traits::{Table, Insert, Upsert, SelectV2};
+//!
+//! #[derive(derive_sql::DeriveSqlStatement)]
+//! #[derive_sql(primary_key(artist_id, artwork_id), unique(artwork_id, position))]
+//! pub struct Credit {
+//!   artist_id: i64,
+//!   artwork_id: i64,
+//!   position: u32,
+//!   role: String,
+//! }
+//!
+//! let mut conn = derive_sql::rusqlite::Connection::open_in_memory().unwrap();
+//! let db = SqlCredit::default();
+//! db.create(&mut conn).unwrap();
+//! db.insert(&mut conn, &Credit { artist_id: 1, artwork_id: 10, position: 0, role: "Painter".to_string() }).unwrap();
+//! db.insert(&mut conn, &Credit { artist_id: 2, artwork_id: 10, position: 1, role: "Painter".to_string() }).unwrap();
+//! assert!(db.insert(&mut conn, &Credit { artist_id: 2, artwork_id: 10, position: 2, role: "Printer".to_string() }).is_err());
+//! assert!(db.insert(&mut conn, &Credit { artist_id: 3, artwork_id: 10, position: 1, role: "Printer".to_string() }).is_err());
+//!
+//! // Upsert conflicts on the composite primary key
+//! db.upsert(&mut conn, &Credit { artist_id: 2, artwork_id: 10, position: 1, role: "Printer".to_string() }).unwrap();
+//! let credits: Vec<Credit> = db.select(&mut conn).unwrap();
+//! assert!(credits.len() == 2 && credits[1].role.eq("Printer"));
+//!
+//! // MySQL refers to the inserted values using the row alias `new`
+//! let mut mock = derive_sql::proxy::Mock::from_flavor(derive_sql::traits::Flavor::MySQL);
+//! db.upsert(&mut mock, &Credit { artist_id: 2, artwork_id: 10, position: 1, role: "Printer".to_string() }).unwrap();
+//! assert!(mock.sql()[0].ends_with(") AS new ON DUPLICATE KEY UPDATE `position` = new.`position`, `role` = new.`role`"));
+//! ```
 //!
 //! # Field attributes:
-//! - `#[derive_sqlite(is_primary_key = true)]` nominate that one of the field is a primary key. Only one primary key can be specified.
+//! - `#[derive_sqlite(is_primary_key = true)]` nominate that one of the field is a primary key. Only one primary key field can be specified: use the container attribute `primary_key(...)` for a composite primary key.
 //! primary key fields are unique in the table. Primary key can NOT be a String - the following will not compile:
 //!
 //! ```compile_fail
@@ -78,8 +111,172 @@
 //! }
 //! ```
 //!
+//! - `#[derive_sqlite(is_unique = true)]` nominate that the field is unique in the table.
+//!
+//! `is_primary_key` and `is_unique` are also accepted as `#[derive_sql(...)]` attributes.
+//!
+//!
+//! - `#[derive_sql(is_primary_key = true, auto_increment = true)]` nominate an integer primary key generated by the database on insert
+//!   (`AUTOINCREMENT` for SQLite, `AUTO_INCREMENT` for MySQL, `GENERATED ALWAYS AS IDENTITY` for PostgreSQL). The field is left out of
+//!   the insert and update statements and of the conversion to parameters. The generated identifier is returned by `ExecuteResult::last_insert_id`
+//!   with SQLite and MySQL, or with the inserted item by `InsertReturning::insert_returning` with SQLite and PostgreSQL:
+//!
+//! ```rust
+//! # use derive_sql::*;
+//! use derive_sql::traits::{Table, Insert, SelectV2};
+//!
+//! #[derive(DeriveSqlStatement)]
+//! pub struct Task {
+//!   #[derive_sql(is_primary_key = true, auto_increment = true)]
+//!   id: i64,
+//!   title: String,
+//! }
+//!
+//! let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+//! let db = SqlTask::default();
+//! db.create(&mut conn).unwrap();
+//! let r = db.insert(&mut conn, &Task { id: 0, title: "Write".to_string() }).unwrap();
+//! assert!(r.last_insert_id() == Some(1));
+//! let r = db.insert(&mut conn, &Task { id: 0, title: "Review".to_string() }).unwrap();
+//! assert!(r.last_insert_id() == Some(2));
+//! let tasks: Vec<Task> = db.select(&mut conn).unwrap();
+//! assert!(tasks[1].id == 2 && tasks[1].title.eq("Review"));
+//! ```
+//!
+//! - `#[derive_sql(column = "...")]` specify the name of the column in the table (default to the name of the field);
+//! - `#[derive_sql(skip)]` exclude the field from the table. The field is set to its `Default` value when read from the database.
+//! - `#[derive_sql(json)]` store the field - of any `serde::Serialize + serde::de::DeserializeOwned` type - as a JSON document
+//!   (`JSON` for MySQL, `JSONB` for PostgreSQL, `TEXT` for SQLite). `None` is stored as SQL `NULL`. Requires feature `json` of `derive-sql`.
+//! - `#[derive_sql(on_insert = ...)]` nominate a function of the type `fn() -> {type}` with `{type}` corresponding to the type of the
+//!   field. The value returned by the function is stored in place of the field value when the item is inserted or upserted.
+//! - `#[derive_sql(on_update = ...)]` nominate a function of the type `fn() -> {type}` with `{type}` corresponding to the type of the
+//!   field. The value returned by the function is stored in place of the field value when the item is updated.
+//! - `#[derive_sql(created_at)]` store the current UTC time on insert in a `NaiveDateTime` or `Option<NaiveDateTime>` field. The column
+//!   is left untouched when an upsert updates an existing item.
+//! - `#[derive_sql(updated_at)]` store the current UTC time on insert and on update in a `NaiveDateTime` or `Option<NaiveDateTime>` field.
+//!
+//! `on_insert` and `on_update` are also accepted as `#[derive_sqlite(...)]` attributes. The generated values are bound in place of the
+//! field values by `Params::as_vec_insert_params` and `Params::as_vec_update_params`: the item itself is not modified.
+//!
+//! ```rust
+//! use derive_sql::traits::{self, Insert, Update};
+//! use derive_sql::chrono::NaiveDateTime;
+//!
+//! fn reference() -> String { "REF-1".to_string() }
+//!
+//! #[derive(derive_sql::DeriveSqlStatement)]
+//! pub struct Order {
+//!   #[derive_sql(on_insert = reference)]
+//!   reference: String,
+//!   #[derive_sql(created_at)]
+//!   created: NaiveDateTime,
+//!   #[derive_sql(updated_at)]
+//!   updated: Option<NaiveDateTime>,
+//! }
+//!
+//! let mut conn = derive_sql::proxy::Mock::from_flavor(traits::Flavor::SQLite);
+//! let db = SqlOrder::default();
+//! let order = Order { reference: String::new(), created: NaiveDateTime::default(), updated: None };
+//! db.insert(&mut conn, &order).unwrap();
+//! assert!(matches!(conn.statements()[0].params(),
+//!   [traits::Param::Text(r), traits::Param::NaiveDateTime(c), traits::Param::NaiveDateTime(_)] if r.eq("REF-1") && *c != NaiveDateTime::default()));
+//!
+//! db.update(&mut conn, &order).unwrap();
+//! assert!(matches!(conn.statements()[1].params(),
+//!   [traits::Param::Text(r), traits::Param::NaiveDateTime(c), traits::Param::NaiveDateTime(_)] if r.is_empty() && *c == NaiveDateTime::default()));
+//! ```
+//!
+//! - `#[derive_sql(references = "table(column)")]` declare a foreign key referencing the column of another table;
+//! - `#[derive_sql(on_delete = "...")]` specify the action - `cascade`, `restrict`, `set null`, `set default` or `no action` - applied
+//!   to the row when the referenced row is deleted. Requires `references`.
+//!
+//! For each foreign key field `{field}`, the wrapper provides `select_by_{field}` to retrieve the items matching a list of keys and
+//! `load_{name}s` - with `{name}` the name of the field without the `_id` suffix, pluralised by appending `s`, eg `load_artists` for
+//! field `artist_id` - to retrieve the items referenced by a list of items, binding each distinct key once.
+//! Both run a single statement, or one statement per batch of keys when the keys exceed the number of parameters supported by the
+//! database (`Flavor::max_params`). Note that SQLite enforces foreign keys only once `PRAGMA foreign_keys = ON` is executed.
+//!
+//! ```rust
+//! use derive_sql::traits::{Table, Insert};
+//!
+//! #[derive(derive_sql::DeriveSqlStatement)]
+//! pub struct Artist {
+//!   #[derive_sql(is_primary_key = true)]
+//!   id: i64,
+//!   name: String,
+//! }
+//!
+//! #[derive(derive_sql::DeriveSqlStatement)]
+//! pub struct Artwork {
+//!   title: String,
+//!   #[derive_sql(references = "artist(id)", on_delete = "cascade")]
+//!   artist_id: i64,
+//! }
+//!
+//! let mut conn = derive_sql::rusqlite::Connection::open_in_memory().unwrap();
+//! conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+//! let (artist_db, artwork_db) = (SqlArtist::default(), SqlArtwork::default());
+//! artist_db.create(&mut conn).unwrap();
+//! artwork_db.create(&mut conn).unwrap();
+//! artist_db.insert(&mut conn, &Artist { id: 1, name: "Monet".to_string() }).unwrap();
+//! artist_db.insert(&mut conn, &Artist { id: 2, name: "Degas".to_string() }).unwrap();
+//! artwork_db.insert(&mut conn, &Artwork { title: "Water Lilies".to_string(), artist_id: 1 }).unwrap();
+//! artwork_db.insert(&mut conn, &Artwork { title: "The Dance Class".to_string(), artist_id: 2 }).unwrap();
+//! assert!(artwork_db.insert(&mut conn, &Artwork { title: "Unknown".to_string(), artist_id: 3 }).is_err());
+//!
+//! let artworks = artwork_db.select_by_artist_id(&mut conn, &[1, 2]).unwrap();
+//! let artists: Vec<Artist> = artwork_db.load_artists(&mut conn, &artist_db, &artworks).unwrap();
+//! assert!(artworks.len() == 2 && artists.len() == 2);
+//!
+//! // Artists referenced by several artworks are bound once
+//! let mut mock = derive_sql::proxy::Mock::from_flavor(derive_sql::traits::Flavor::SQLite);
+//! let artworks = vec![Artwork { title: "Water Lilies".to_string(), artist_id: 1 },
+//!                     Artwork { title: "Haystacks".to_string(), artist_id: 1 }];
+//! let _: Vec<Artist> = artwork_db.load_artists(&mut mock, &artist_db, &artworks).unwrap();
+//! assert!(mock.statements()[0].params().len() == 1);
+//!
+//! // Artworks are deleted with their artist
+//! artist_db.delete_by_key(&mut conn, 1).unwrap();
+//! assert!(artwork_db.select_by_artist_id(&mut conn, &[1]).unwrap().is_empty());
+//! ```
+//!
+//! Fields of a type not supported natively - such as a newtype or an enum - are converted using the `derive_sql::traits::SqlColumnType`
+//! trait implementation of the type.
+//!
+//! The upsert statements (`UpsertFlavoredStatement`: insert or update, insert or ignore) are implemented when a primary key or unique
+//! constraint is nominated. Conflicts are detected on the primary key or - if none - on the first unique field or constraint.
+//!
+//! When a primary key is nominated, the wrapper provides `get`, `exists`, `update_by_key` and `delete_by_key` to query, update and delete
+//! an item by key. The key is the value of the primary key field, or a tuple of the values of a composite primary key.
+//! `update_by_key` and `delete_by_key` are not available on `read_only` wrappers.
+//!
+//! ```rust
+//! use derive_sql::traits::{Table, Insert};
+//!
+//! #[derive(derive_sql::DeriveSqlStatement)]
+//! pub struct Artist {
+//!   #[derive_sql(is_primary_key = true, auto_increment = true)]
+//!   id: i64,
+//!   name: String,
+//! }
+//!
+//! let mut conn = derive_sql::rusqlite::Connection::open_in_memory().unwrap();
+//! let db = SqlArtist::default();
+//! db.create(&mut conn).unwrap();
+//! db.insert(&mut conn, &Artist { id: 0, name: "Monet".to_string() }).unwrap();
+//!
+//! let mut artist = db.get(&mut conn, 1).unwrap().unwrap();
+//! artist.name = "Claude Monet".to_string();
+//! db.update_by_key(&mut conn, &artist).unwrap();
+//! assert!(db.get(&mut conn, 1).unwrap().is_some_and(|a| a.name.eq("Claude Monet")));
+//!
+//! db.delete_by_key(&mut conn, 1).unwrap();
+//! assert!(! db.exists(&mut conn, 1).unwrap());
+//! ```
+//!
 
 mod statement;
+mod sql_enum;
 
 use attribute_derive::{Attribute};
 
@@ -91,6 +288,43 @@ struct Attrs {
   read_only: bool,
 }
 
+/// Container attributes listing fields: `primary_key(a, b)` and `unique(a, b)`
+struct Constraints {
+  primary_key: Option<Vec<syn::Ident>>,
+  unique: Vec<Vec<syn::Ident>>,
+}
+
+/// Parse the container attributes. The lists of fields are not supported by `attribute_derive` and are extracted
+/// before the remaining arguments are parsed into `Attrs`
+fn container_attributes(attributes: &[syn::Attribute]) -> syn::parse::Result<(Attrs, Constraints)> {
+  let mut constraints = Constraints { primary_key: None, unique: Vec::new() };
+  let mut args = Vec::new();
+  for attribute in attributes.iter().filter(|a| a.path().is_ident("derive_sql")) {
+    let metas = attribute.parse_args_with(syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)?;
+    for meta in metas {
+      match meta {
+        syn::Meta::List(list) if list.path.is_ident("primary_key") => {
+          if constraints.primary_key.is_some() {
+            return Err(syn::Error::new_spanned(&list, "Only one `primary_key(...)` can be nominated."));
+          }
+          constraints.primary_key = Some(field_list(&list)?);
+        },
+        syn::Meta::List(list) if list.path.is_ident("unique") => constraints.unique.push(field_list(&list)?),
+        meta => args.push(meta),
+      }
+    }
+  }
+  Ok((Attrs::from_args(quote::quote! { #( #args ),* })?, constraints))
+}
+
+fn field_list(list: &syn::MetaList) -> syn::parse::Result<Vec<syn::Ident>> {
+  let fields = list.parse_args_with(syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated)?;
+  if fields.is_empty() {
+    return Err(syn::Error::new_spanned(list, "At least one field must be nominated."));
+  }
+  Ok(fields.into_iter().collect())
+}
+
 #[proc_macro_derive(DeriveSqlStatement, attributes(derive_sql, derive_sqlite))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
   syn::parse(input)
@@ -101,3 +335,47 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
   .into()
 }
 
+/// Procedural macro to store a fieldless enum in a column: implements `SqlColumnType`, `ToParam` and `TryFromValue` so that the enum
+/// can be used as a `DeriveSqlStatement` field, as a statement parameter and as a query result.
+///
+/// Container attribute:
+/// - `#[derive_sql(repr = "text" | "int")]` store the variant name (default) or the variant discriminant.
+///
+/// Variant attribute:
+/// - `#[derive_sql(rename = "...")]` specify the value stored for the variant with the `text` representation.
+///
+/// Values read from the database that do not match any variant return `Error::UnknownEnumValue`.
+///
+/// ```rust
+/// use derive_sql::traits::{self, SqlColumnType};
+///
+/// #[derive(Debug, PartialEq, derive_sql::DeriveSqlEnum)]
+/// enum Status {
+///   #[derive_sql(rename = "active")]
+///   Active,
+///   #[derive_sql(rename = "archived")]
+///   Archived,
+/// }
+///
+/// #[derive(Debug, PartialEq, derive_sql::DeriveSqlEnum)]
+/// #[derive_sql(repr = "int")]
+/// enum Priority {
+///   Low = 1,
+///   High = 10,
+/// }
+///
+/// assert!(matches!(Status::Archived.to_param(), Ok(traits::Param::Text(s)) if s.eq("archived")));
+/// assert!(Status::try_from_value(traits::Value::Text("active".to_string())).unwrap() == Status::Active);
+/// assert!(matches!(Status::try_from_value(traits::Value::Text("deleted".to_string())), Err(derive_sql::Error::UnknownEnumValue(_, _))));
+/// assert!(matches!(Priority::High.to_param(), Ok(traits::Param::BigInt(10))));
+/// assert!(Priority::try_from_value(traits::Value::Integer(1)).unwrap() == Priority::Low);
+/// ```
+#[proc_macro_derive(DeriveSqlEnum, attributes(derive_sql))]
+pub fn derive_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  syn::parse(input)
+  .and_then(|ast: syn::DeriveInput| {
+    sql_enum::SqlEnum::try_from(&ast)?.generate()
+  })
+  .unwrap_or_else(|e| e.into_compile_error())
+  .into()
+}
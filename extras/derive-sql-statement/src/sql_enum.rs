@@ -0,0 +1,124 @@
+use super::*;
+
+#[derive(Attribute)]
+#[attribute(ident = derive_sql)]
+struct EnumAttrs {
+  repr: Option<String>,
+}
+
+#[derive(Attribute)]
+#[attribute(ident = derive_sql)]
+struct VariantAttrs {
+  rename: Option<String>,
+}
+
+enum Repr {
+  Text,
+  Int,
+}
+
+pub struct SqlEnum<'a> {
+  ast: &'a syn::DeriveInput,
+  variants: Vec<&'a syn::Variant>,
+}
+
+impl<'a> TryFrom<&'a syn::DeriveInput> for SqlEnum<'a> {
+  type Error = syn::parse::Error;
+  fn try_from(ast: &'a syn::DeriveInput) -> syn::parse::Result<SqlEnum<'a>> {
+    match &ast.data {
+      syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+        if let Some(v) = variants.iter().find(|v| ! matches!(v.fields, syn::Fields::Unit)) {
+          return Err(syn::Error::new(v.ident.span(), "Procedural macro DeriveSqlEnum is intended to be applied to enum without fields."));
+        }
+        Ok(SqlEnum { ast, variants: variants.iter().collect() })
+      },
+      _ => Err(syn::Error::new(ast.ident.span(), "Procedural macro DeriveSqlEnum is intended to be applied to enum without fields.")),
+    }
+  }
+}
+
+impl<'a> SqlEnum<'a> {
+  pub fn generate(self) -> syn::parse::Result<proc_macro2::TokenStream> {
+    let attrs = EnumAttrs::from_attributes(&self.ast.attrs)?;
+    let ident = &self.ast.ident;
+    let name = format!("{ident}");
+    let repr = match attrs.repr.as_deref() {
+      None | Some("text") => Repr::Text,
+      Some("int") => Repr::Int,
+      Some(repr) => return Err(syn::Error::new(ident.span(), format!("Representation `{repr}` is not supported. Use `text` or `int`."))),
+    };
+
+    let variants = self.variants.iter().map(|v| v.ident.clone()).collect::<Vec<syn::Ident>>();
+
+    let (raw_type, to_param, try_from_value) = match repr {
+      Repr::Text => {
+        let values = self.variants.iter()
+          .map(|v| Ok(VariantAttrs::from_attributes(&v.attrs)?.rename.unwrap_or_else(|| format!("{}", v.ident))))
+          .collect::<syn::parse::Result<Vec<String>>>()?;
+        (
+          quote::quote! { flavor.raw_type("String") },
+          quote::quote! {
+            match self {
+              #( #ident::#variants => Ok(derive_sql::traits::Param::Text(#values.to_string())), )*
+            }
+          },
+          quote::quote! {
+            let v: String = derive_sql::traits::TryFromValue::try_from(v)?;
+            match v.as_str() {
+              #( #values => Ok(#ident::#variants), )*
+              _ => Err(derive_sql::Error::UnknownEnumValue(#name.to_string(), v)),
+            }
+          },
+        )
+      },
+      Repr::Int => {
+        if let Some(v) = self.variants.iter().find(|v| VariantAttrs::from_attributes(&v.attrs).map(|a| a.rename.is_some()).unwrap_or(false)) {
+          return Err(syn::Error::new(v.ident.span(), "Variant `rename` is not supported with `int` representation. Use the variant discriminant instead."));
+        }
+        (
+          quote::quote! { flavor.raw_type("i64") },
+          quote::quote! {
+            match self {
+              #( #ident::#variants => Ok(derive_sql::traits::Param::BigInt(#ident::#variants as i64)), )*
+            }
+          },
+          quote::quote! {
+            let v: i64 = derive_sql::traits::TryFromValue::try_from(v)?;
+            match v {
+              #( v if v == #ident::#variants as i64 => Ok(#ident::#variants), )*
+              _ => Err(derive_sql::Error::UnknownEnumValue(#name.to_string(), v.to_string())),
+            }
+          },
+        )
+      },
+    };
+
+    Ok(quote::quote! {
+      impl derive_sql::traits::SqlColumnType for #ident {
+        fn raw_type(flavor: &derive_sql::traits::Flavor) -> derive_sql::Result<derive_sql::traits::RawType> {
+          #raw_type
+        }
+
+        fn to_param(&self) -> derive_sql::Result<derive_sql::traits::Param> {
+          #to_param
+        }
+
+        fn try_from_value(v: derive_sql::traits::Value) -> derive_sql::Result<Self> {
+          #try_from_value
+        }
+      }
+
+      impl derive_sql::traits::ToParam for #ident {
+        fn to_param(&self) -> derive_sql::Result<derive_sql::traits::Param> {
+          derive_sql::traits::SqlColumnType::to_param(self)
+        }
+      }
+
+      impl derive_sql::traits::TryFromValue for #ident {
+        fn try_from(v: derive_sql::traits::Value) -> derive_sql::Result<Self> {
+          derive_sql::traits::SqlColumnType::try_from_value(v)
+        }
+      }
+    })
+  }
+}
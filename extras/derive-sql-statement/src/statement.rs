@@ -20,36 +20,91 @@ impl<'a> TryFrom<&'a syn::DeriveInput> for Statement<'a> {
 
 impl<'a> Statement<'a> {
   pub fn generate(self) -> syn::parse::Result<proc_macro2::TokenStream> {
-    let attrs = Attrs::from_attributes(&self.ast.attrs)?;
+    let (attrs, constraints) = container_attributes(&self.ast.attrs)?;
     let vis  = &self.ast.vis;
     let ident = &self.ast.ident;
     let sql_ident  = attrs.ident.as_ref().map(|i| i.clone()).unwrap_or_else(|| quote::format_ident!("Sql{ident}"));
     let table_name = attrs.table_name.as_ref().map(|i| i.clone()).unwrap_or_else(|| format!("{ident}").to_lowercase());
 
-    let fields = self.fields_named.named.iter()
+    let (skipped_fields, fields): (Vec<fields::Fields>, Vec<fields::Fields>) = self.fields_named.named.iter()
       .map(|f| f.try_into().map_err(|e| syn::Error::new(ident.span(), format!("{e}"))))
-      .collect::<Result<Vec<fields::Fields>, syn::Error>>()?;
+      .collect::<Result<Vec<fields::Fields>, syn::Error>>()?
+      .into_iter()
+      .partition(|f| f.is_skip());
+
+    // Primary key and unique constraints nominated on fields or - for multiple fields - on the container
+    let find = |i: &syn::Ident| fields.iter().find(|f| f.ident() == i)
+      .ok_or_else(|| syn::Error::new(i.span(), format!("Field `{i}` is not a column of the table.")));
+    let field_primary_key = fields.iter().filter(|f| f.is_primary_key()).collect::<Vec<&fields::Fields>>();
+    if field_primary_key.len() > 1 {
+      return Err(syn::Error::new(self.ast.ident.span(), "Only one field can be nominated `is_primary_key`. Use `#[derive_sql(primary_key(...))]` for a composite primary key."));
+    }
+    let primary_key = match &constraints.primary_key {
+      Some(idents) => {
+        if let Some(f) = field_primary_key.first() {
+          return Err(syn::Error::new(self.ast.ident.span(), format!("Field `{}` error: `is_primary_key` can not be combined with `primary_key(...)`.", f.name())));
+        }
+        idents.iter().map(find).collect::<syn::parse::Result<Vec<&fields::Fields>>>()?
+      },
+      None => field_primary_key,
+    };
+    let unique = fields.iter().filter(|f| f.is_unique()).map(|f| Ok(vec![f]))
+      .chain(constraints.unique.iter().map(|idents| idents.iter().map(find).collect::<syn::parse::Result<Vec<&fields::Fields>>>()))
+      .collect::<syn::parse::Result<Vec<Vec<&fields::Fields>>>>()?;
 
     // Primary key of SQL type TEXT is not supported
-    if let Some(name) = fields.iter().fold(None, |r, f| r.or_else(|| if f.is_primary_key() && f.raw_type().eq("String") { Some(f.name()) } else { None })) {
+    if let Some(name) = primary_key.iter().find(|f| f.raw_type().eq("String")).map(|f| f.name()) {
       return Err(syn::Error::new(self.ast.ident.span(), format!("Field `{name}` error: Use of String, Option<String> primary key is not supported in `derive-sql` feature.")));
     }
 
+    // Auto increment is only supported on integer primary key
+    if let Some(f) = fields.iter().find(|f| f.is_auto_increment() && ! (f.is_primary_key() && matches!(f.sql_type(), SqlType::Integer | SqlType::OptionInteger))) {
+      return Err(syn::Error::new(self.ast.ident.span(), format!("Field `{}` error: `auto_increment` is only supported on an integer primary key.", f.name())));
+    }
+
+    if let Some(f) = fields.iter().find(|f| (f.is_created_at() || f.is_updated_at()) && f.raw_type() != "NaiveDateTime") {
+      return Err(syn::Error::new(self.ast.ident.span(), format!("Field `{}` error: `created_at` and `updated_at` are only supported on `NaiveDateTime` or `Option<NaiveDateTime>` fields.", f.name())));
+    }
+
+    // Fields bound on insert and update - ie excluding the fields generated by the database
+    let insert_fields = fields.iter().filter(|f| ! f.is_auto_increment()).collect::<Vec<&fields::Fields>>();
+
     // List of token that convert column names with flavor
     let columns = fields.iter()
         .map(|f| {
-          let ident = f.ident(); let s = format!("{ident}");
+          let ident = f.ident(); let s = f.column_name();
           Ok(quote::quote! { #ident = conn.flavor().column(#s)? })
         })
         .collect::<syn::parse::Result<Vec<proc_macro2::TokenStream>>>()?;
+    let insert_columns = fields.iter().zip(columns.iter())
+        .filter_map(|(f, c)| if f.is_auto_increment() { None } else { Some(c) })
+        .collect::<Vec<&proc_macro2::TokenStream>>();
      let columns_types = fields.iter()
        .map(|f| {
          let ident = f.ident(); let ident_type = quote::format_ident!("{ident}_type");
          let ty = f.raw_type().to_string();
-         Ok(quote::quote! { let #ident_type = conn.flavor().sql_type(#ty)?; })
+         let nullable = f.is_nullable();
+         if f.is_auto_increment() {
+           Ok(quote::quote! { let #ident_type = conn.flavor().auto_increment_sql_type(#ty)?; })
+         } else if f.sql_type().is_json() {
+           Ok(quote::quote! { let #ident_type = conn.flavor().json_sql_type(#nullable)?; })
+         } else if f.sql_type().is_custom() {
+           let ty = f.ty();
+           Ok(quote::quote! { let #ident_type = conn.flavor().column_sql_type::<#ty>(#nullable)?; })
+         } else {
+           Ok(quote::quote! { let #ident_type = conn.flavor().sql_type_nullable(#ty, #nullable)?; })
+         }
        })
        .collect::<syn::parse::Result<Vec<proc_macro2::TokenStream>>>()?;
 
+    // Referenced table and column of foreign keys, with flavor
+    let references = fields.iter()
+      .filter_map(|f| f.references().map(|(table, column)| {
+        let ident_references = quote::format_ident!("{}_references", f.ident());
+        quote::quote! { let #ident_references = format!("{} ( {} )", conn.flavor().table(#table)?, conn.flavor().column(#column)?); }
+      }))
+      .collect::<Vec<proc_macro2::TokenStream>>();
+
     let declaration = {
       let doc = format!("Wrapper struct to query item of type `{ident}` from SQL databases using `derive-sql` crate");
       quote::quote! {
@@ -67,6 +122,128 @@ impl<'a> Statement<'a> {
       }
     };
 
+    let key_members = if primary_key.is_empty() { quote::quote! {} } else {
+      let key_name = primary_key.iter().map(|f| f.column_name()).collect::<Vec<String>>().join("`, `");
+      let key_type = match primary_key.as_slice() {
+        [f] => { let ty = f.ty(); quote::quote! { #ty } },
+        fields => { let tys = fields.iter().map(|f| f.ty()).collect::<Vec<&syn::Type>>(); quote::quote! { ( #( #tys ),* ) } },
+      };
+      // Condition on the key columns with parameters numbered from `first`
+      let key_clause = |first: usize| {
+        let conditions = primary_key.iter().enumerate()
+          .map(|(i, f)| { let s = f.column_name(); quote::quote! { format!("{} = {}", conn.flavor().column(#s)?, conn.flavor().value(#first + #i)?) } })
+          .collect::<Vec<proc_macro2::TokenStream>>();
+        quote::quote! { vec![ #( #conditions ),* ].join(" AND ") }
+      };
+      let key_params = primary_key.iter().enumerate()
+        .map(|(i, f)| {
+          if primary_key.len() == 1 { to_param(f, quote::quote! { key }) }
+          else { let i = syn::Index::from(i); to_param(f, quote::quote! { key.#i }) }
+        })
+        .collect::<Vec<proc_macro2::TokenStream>>();
+      let where_key = key_clause(0);
+
+      let doc_get = format!("Retrieve the item with the given `{key_name}` from the table `{table_name}`");
+      let doc_exists = format!("Whether an item with the given `{key_name}` exists in the table `{table_name}`");
+      let read = quote::quote! {
+        #[doc = #doc_get]
+        pub fn get<C, R>(&self, conn: &mut C, key: #key_type) -> derive_sql::Result<Option<#ident>>
+        where C: derive_sql::traits::Connection<R>,
+              R: derive_sql::traits::Row,
+        {
+          let statement = format!("{} WHERE {}", derive_sql::traits::SelectFlavoredStatement::select_stmt(self, conn)?, #where_key);
+          let params = vec![ #( #key_params ),* ];
+          conn.query_first_with_params_try_as_object(statement, &params)
+        }
+
+        #[doc = #doc_exists]
+        pub fn exists<C, R>(&self, conn: &mut C, key: #key_type) -> derive_sql::Result<bool>
+        where C: derive_sql::traits::Connection<R>,
+              R: derive_sql::traits::Row,
+        {
+          let statement = format!("SELECT 1 FROM {} WHERE {}", conn.flavor().table(#table_name)?, #where_key);
+          let params = vec![ #( #key_params ),* ];
+          Ok(conn.query_first_with_params(statement, &params)?.is_some())
+        }
+      };
+
+      if attrs.read_only { read } else {
+        let object_key_params = primary_key.iter()
+          .map(|f| { let field = f.ident(); to_param(f, quote::quote! { object.#field }) })
+          .collect::<Vec<proc_macro2::TokenStream>>();
+        let where_object_key = key_clause(insert_fields.len());
+        let doc_update = format!("Update the item with the same `{key_name}` as the given item in the table `{table_name}`");
+        let doc_delete = format!("Delete the item with the given `{key_name}` from the table `{table_name}`");
+        quote::quote! {
+          #read
+
+          #[doc = #doc_update]
+          pub fn update_by_key<C, R>(&self, conn: &mut C, object: &#ident) -> derive_sql::Result<derive_sql::traits::ExecuteResult>
+          where C: derive_sql::traits::Connection<R>,
+                R: derive_sql::traits::Row,
+          {
+            let statement = format!("{} WHERE {}", derive_sql::traits::UpdateFlavoredStatement::update_stmt(self, conn)?, #where_object_key);
+            let mut params = derive_sql::traits::Params::as_vec_update_params(object)?;
+            params.extend(vec![ #( #object_key_params ),* ]);
+            conn.execute_with_params(statement, &params)
+          }
+
+          #[doc = #doc_delete]
+          pub fn delete_by_key<C, R>(&self, conn: &mut C, key: #key_type) -> derive_sql::Result<derive_sql::traits::ExecuteResult>
+          where C: derive_sql::traits::Connection<R>,
+                R: derive_sql::traits::Row,
+          {
+            let statement = format!("{} WHERE {}", derive_sql::traits::DeleteFlavoredStatement::delete_stmt(self, conn)?, #where_key);
+            let params = vec![ #( #key_params ),* ];
+            conn.execute_with_params(statement, &params)
+          }
+        }
+      }
+    };
+
+    // Batch loading of the items referenced by - and referencing - foreign key fields
+    let relation_members = fields.iter()
+      .filter_map(|f| f.references().map(|(table, column)| (f, table, column)))
+      .map(|(f, table, column)| {
+        let field = f.ident(); let ty = f.ty(); let column_name = f.column_name();
+        let name = f.name();
+        let load = quote::format_ident!("load_{}s", name.strip_suffix("_id").unwrap_or(name.as_str()));
+        let select_by = quote::format_ident!("select_by_{}", name);
+        let item_param = to_param(f, quote::quote! { item.#field });
+        let key_param = to_param(f, quote::quote! { (*key) });
+        let doc_load = format!("Retrieve in a single statement - or one per batch of `Flavor::max_params` keys - the items of table `{table}` referenced by field `{name}` of the given items. \
+          `parent` is the wrapper of the referenced items. Keys referenced by several items are bound once.");
+        let doc_select_by = format!("Retrieve in a single statement - or one per batch of `Flavor::max_params` keys - the items of table `{table_name}` with field `{name}` - referencing `{table}({column})` - \
+          matching one of the given keys.");
+        quote::quote! {
+          #[doc = #doc_load]
+          pub fn #load<S, P, C, R>(&self, conn: &mut C, parent: &S, items: &[#ident]) -> derive_sql::Result<Vec<P>>
+          where S: derive_sql::traits::SelectByKeys<C, R, P>,
+                C: derive_sql::traits::Connection<R>,
+                R: derive_sql::traits::Row,
+          {
+            let mut keys: Vec<derive_sql::traits::Param> = Vec::new();
+            for item in items {
+              let key = #item_param;
+              if ! matches!(key, derive_sql::traits::Param::Null) && ! keys.contains(&key) { keys.push(key); }
+            }
+            parent.select_by_keys(conn, #column, &keys)
+          }
+
+          #[doc = #doc_select_by]
+          pub fn #select_by<C, R>(&self, conn: &mut C, keys: &[#ty]) -> derive_sql::Result<Vec<#ident>>
+          where C: derive_sql::traits::Connection<R>,
+                R: derive_sql::traits::Row,
+          {
+            let keys = keys.iter()
+              .map(|key| Ok(#key_param))
+              .collect::<derive_sql::Result<Vec<derive_sql::traits::Param>>>()?;
+            derive_sql::traits::SelectByKeys::select_by_keys(self, conn, #column_name, &keys)
+          }
+        }
+      })
+      .collect::<Vec<proc_macro2::TokenStream>>();
+
     let table_statement = {
       let statement = format!("{}",
         {
@@ -74,18 +251,27 @@ impl<'a> Statement<'a> {
           .map(|f| Ok(format!("{{{ident}}} {{{ident}_type}}", ident = f.ident()))) //, sql_type = f.sql_type().to_string())) )
           .collect::<syn::parse::Result<Vec<String>>>()?;
 
-          if let Some(primary_key) = fields.iter().fold(None, |r, f| r.or_else(|| if f.is_primary_key() { Some(f) } else { None })) {
-            a.push(format!("PRIMARY KEY ( `{}` )", primary_key.ident()));
+          let placeholders = |fields: &[&fields::Fields]| fields.iter().map(|f| format!("{{{}}}", f.ident())).collect::<Vec<String>>().join(", ");
+          // Auto increment primary key is declared with the column
+          if ! primary_key.is_empty() && ! primary_key.iter().any(|f| f.is_auto_increment()) {
+            a.push(format!("PRIMARY KEY ( {} )", placeholders(&primary_key)));
+          }
+          for u in unique.iter() {
+            a.push(format!("CONSTRAINT {}_unique UNIQUE ( {} )", u.iter().map(|f| f.column_name()).collect::<Vec<String>>().join("_"), placeholders(u)));
           }
-          for f in fields.iter().filter(|f| f.is_unique()) {
-            a.push(format!("CONSTRAINT {0}_unique UNIQUE ( `{0}` )", f.ident()));
+          for f in fields.iter().filter(|f| f.references().is_some()) {
+            let on_delete = f.on_delete().map(|a| format!(" ON DELETE {a}")).unwrap_or_default();
+            a.push(format!("FOREIGN KEY ( {{{ident}}} ) REFERENCES {{{ident}_references}}{on_delete}", ident = f.ident()));
           }
           a.join(", ")
         }
       );
 
-      let doc = format!("Create table `{table_name}` statement<br/>SQL statement:<br/>```CREATE TABLE {table_name} ( {statement} )```",
-        statement = statement.replace("{","").replace("}",""));
+      let doc_statement = fields.iter()
+        .filter_map(|f| f.references().map(|(table, column)| (f, table, column)))
+        .fold(statement.clone(), |s, (f, table, column)| s.replace(format!("{{{}_references}}", f.ident()).as_str(), format!("{table} ( {column} )").as_str()))
+        .replace("{","").replace("}","");
+      let doc = format!("Create table `{table_name}` statement<br/>SQL statement:<br/>```CREATE TABLE {table_name} ( {doc_statement} )```");
       let create_stmt = quote::quote! {
         #[doc = #doc]
         fn create_stmt<C, R>(&self, conn: &C) -> derive_sql::Result<String>
@@ -94,6 +280,7 @@ impl<'a> Statement<'a> {
         {
           #(let #columns ; )*
           #(#columns_types)*
+          #(#references)*
           Ok(format!("CREATE TABLE {table_name} ( {statement} )", 
             table_name = conn.flavor().table(#table_name)?, 
             statement = format!(#statement),
@@ -101,8 +288,7 @@ impl<'a> Statement<'a> {
         }
       };
 
-      let doc = format!("Create table if not exists `{table_name}` statement<br/>SQL statement:<br/>```CREATE TABLE IF NOT EXISTS {table_name} ( {statement} )```",
-        statement = statement.replace("{","").replace("}",""));
+      let doc = format!("Create table if not exists `{table_name}` statement<br/>SQL statement:<br/>```CREATE TABLE IF NOT EXISTS {table_name} ( {doc_statement} )```");
       let create_if_not_exists_stmt = quote::quote! {
         #[doc = #doc]
         fn create_if_not_exist_stmt<C, R>(&self, conn: &C) -> derive_sql::Result<String> 
@@ -111,6 +297,7 @@ impl<'a> Statement<'a> {
         {
           #(let #columns ; )*
           #(#columns_types)*
+          #(#references)*
           Ok(format!("CREATE TABLE IF NOT EXISTS {table_name} ( {statement} )", 
             table_name = conn.flavor().table(#table_name)?, 
             statement = format!(#statement),
@@ -163,9 +350,9 @@ impl<'a> Statement<'a> {
     };
 
     let insert_statement = {
-      let columns_stmt = fields.iter().map(|f| format!("{{{ident}}}",ident = f.ident())).collect::<Vec<String>>().join(", ");
-      let values_stmt = fields.iter().map(|_| format!("?")).collect::<Vec<String>>().join(", ");
-      let values = fields.iter().enumerate().map(|(i,_)| quote::quote! { conn.flavor().value(#i)? }).collect::<Vec<proc_macro2::TokenStream>>();
+      let columns_stmt = insert_fields.iter().map(|f| format!("{{{ident}}}",ident = f.ident())).collect::<Vec<String>>().join(", ");
+      let values_stmt = insert_fields.iter().map(|_| format!("?")).collect::<Vec<String>>().join(", ");
+      let values = insert_fields.iter().enumerate().map(|(i,_)| quote::quote! { conn.flavor().value(#i)? }).collect::<Vec<proc_macro2::TokenStream>>();
       /*
       let statement = format!("INSERT INTO {table_name} ({}) VALUES ({})",
         fields.iter().map(|f| format!("`{}`",f.name())).collect::<Vec<String>>().join(", "),
@@ -185,7 +372,7 @@ impl<'a> Statement<'a> {
           {
             Ok(format!("INSERT INTO {table_name} ({columns}) VALUES ({values})", 
               table_name = conn.flavor().table(#table_name)?,
-              columns = format!(#columns_stmt, #(#columns, )*),
+              columns = format!(#columns_stmt, #(#insert_columns, )*),
               values = vec![#(#values, )*].join(", "),
             ))
           }
@@ -194,15 +381,15 @@ impl<'a> Statement<'a> {
     };
 
     let update_statement = {
-      let statement = fields.iter().map(|f| format!("{{{ident}}} = {{{ident}_value}}",ident = f.ident())).collect::<Vec<String>>().join(", ");
-      let values  = fields.iter().enumerate()
+      let statement = insert_fields.iter().map(|f| format!("{{{ident}}} = {{{ident}_value}}",ident = f.ident())).collect::<Vec<String>>().join(", ");
+      let values  = insert_fields.iter().enumerate()
         .map(|(i,f)| {
           let ident = quote::format_ident!("{ident}_value", ident = f.ident());
           quote::quote! { #ident = conn.flavor().value(#i)? }
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
       let doc = format!("Update item(s) nominated by the selector in the table {table_name}<br/>SQL statement:<br/>```UPDATE {table_name} SET {statement}```",
-        statement = fields.iter().map(|f| format!("{ident} = ?", ident = f.ident())).collect::<Vec<String>>().join(", "));
+        statement = insert_fields.iter().map(|f| format!("{column} = ?", column = f.column_name())).collect::<Vec<String>>().join(", "));
 
       quote::quote! {
         impl derive_sql::traits::UpdateFlavoredStatement for #sql_ident {
@@ -212,7 +399,7 @@ impl<'a> Statement<'a> {
                 R: derive_sql::traits::Row
           {
             #(let #values ; )*
-            #(let #columns ; )*
+            #(let #insert_columns ; )*
             Ok(format!("UPDATE {table_name} SET {statement}", 
               table_name = conn.flavor().table(#table_name)?,
               statement = format!(#statement),
@@ -235,23 +422,115 @@ impl<'a> Statement<'a> {
       }
     };
 
+    let upsert_statement = {
+      // Conflict on the primary key or - if none or generated by the database - on the first unique constraint
+      let key = if ! primary_key.is_empty() && ! primary_key.iter().any(|f| f.is_auto_increment()) { Some(&primary_key) } else { unique.first() };
+      if let Some(key) = key {
+        let key_name = key.iter().map(|f| f.column_name()).collect::<Vec<String>>().join("`, `");
+        let key_columns = key.iter()
+          .map(|f| { let s = f.column_name(); quote::quote! { conn.flavor().column(#s)? } })
+          .collect::<Vec<proc_macro2::TokenStream>>();
+        // Values generated only on insert - such as `created_at` - are kept on update
+        let update_columns = insert_fields.iter()
+          .filter(|f| ! key.iter().any(|k| k.ident() == f.ident()))
+          .filter(|f| ! (on_insert_value(f).is_some() && on_update_value(f).is_none()))
+          .map(|f| { let s = f.column_name(); quote::quote! { conn.flavor().column(#s)? } })
+          .collect::<Vec<proc_macro2::TokenStream>>();
+        let doc = format!("Insert an item {ident} into the database table {table_name} or update the existing item with the same `{key_name}`");
+        let doc_ignore = format!("Insert an item {ident} into the database table {table_name} unless an item with the same `{key_name}` exists");
+        quote::quote! {
+          impl derive_sql::traits::UpsertFlavoredStatement for #sql_ident {
+            #[doc = #doc]
+            fn upsert_stmt<C, R>(&self, conn: &C) -> derive_sql::Result<String>
+            where C: derive_sql::traits::Connection<R>,
+                  R: derive_sql::traits::Row,
+            {
+              Ok(format!("{insert} {on_conflict}",
+                insert = derive_sql::traits::InsertFlavoredStatement::insert_stmt(self, conn)?,
+                on_conflict = conn.flavor().on_conflict_update(&[#(#key_columns, )*], &[#(#update_columns, )*])?,
+              ))
+            }
+
+            #[doc = #doc_ignore]
+            fn insert_or_ignore_stmt<C, R>(&self, conn: &C) -> derive_sql::Result<String>
+            where C: derive_sql::traits::Connection<R>,
+                  R: derive_sql::traits::Row,
+            {
+              Ok(format!("{insert} {on_conflict}",
+                insert = derive_sql::traits::InsertFlavoredStatement::insert_stmt(self, conn)?,
+                on_conflict = conn.flavor().on_conflict_ignore(&[#(#key_columns, )*])?,
+              ))
+            }
+          }
+        }
+      } else {
+        quote::quote! {}
+      }
+    };
+
+    let returning_statement = {
+      let statement = fields.iter()
+        .map(|f| format!("{{{ident}}}", ident = f.ident())).collect::<Vec<String>>()
+        .join(", ");
+      let doc = format!("Clause to retrieve `{ident}` items inserted, updated or deleted in database table `{table_name}`.<br/>SQL statement:<br/>```RETURNING {statement}```",
+        statement = statement.replace("{","").replace("}",""),
+      );
+      quote::quote! {
+        impl derive_sql::traits::ReturningFlavoredStatement for #sql_ident {
+          #[doc = #doc]
+          fn returning_stmt<C, R>(&self, conn: &C) -> derive_sql::Result<String>
+          where C: derive_sql::traits::Connection<R>,
+                R: derive_sql::traits::Row,
+          {
+            Ok(format!("RETURNING {statement}",
+              statement = format!(#statement, #(#columns, )*),
+            ))
+          }
+        }
+      }
+    };
+
     let to_params = {
-      let items = fields.iter()
-      .map(|f| {
-        let field = f.ident();
-        quote::quote! { self.#field.to_param()? }
-      })
+      let items = insert_fields.iter()
+      .map(|f| { let field = f.ident(); to_param(f, quote::quote! { self.#field }) })
       .collect::<Vec<proc_macro2::TokenStream>>();
+      // Parameters with the values generated by the `on_insert` and `on_update` functions
+      let hooked_params = |name: proc_macro2::TokenStream, value: &dyn Fn(&fields::Fields) -> Option<proc_macro2::TokenStream>, timestamp: &dyn Fn(&fields::Fields) -> bool| {
+        if insert_fields.iter().all(|f| value(f).is_none()) { return quote::quote! {}; }
+        // The current time is taken once so that all timestamps of the statement are identical
+        let now = if insert_fields.iter().any(|f| timestamp(f)) {
+          quote::quote! { let now = derive_sql::chrono::Utc::now().naive_utc(); }
+        } else { quote::quote! {} };
+        let items = insert_fields.iter()
+        .map(|f| { let field = f.ident(); to_param(f, value(f).unwrap_or_else(|| quote::quote! { self.#field })) })
+        .collect::<Vec<proc_macro2::TokenStream>>();
+        quote::quote! {
+          fn #name(&self) -> derive_sql::Result<Vec<derive_sql::traits::Param>> {
+            #now
+            Ok(
+              vec![
+                #( #items ),*
+              ]
+            )
+          }
+        }
+      };
+      let insert_params = hooked_params(quote::quote! { as_vec_insert_params }, &on_insert_value,
+        &|f| f.on_insert().is_none() && (f.is_created_at() || f.is_updated_at()));
+      let update_params = hooked_params(quote::quote! { as_vec_update_params }, &on_update_value,
+        &|f| f.on_update().is_none() && f.is_updated_at());
       quote::quote! {
         impl derive_sql::traits::Params for #ident {
           fn as_vec_params(&self) -> derive_sql::Result<Vec<derive_sql::traits::Param>> {
-            use derive_sql::traits::ToParam;
             Ok(
               vec![
                 #( #items ),*
               ]
             )
           }
+
+          #insert_params
+          #update_params
         }
       }
     };
@@ -260,8 +539,18 @@ impl<'a> Statement<'a> {
       let fields_assignment = fields.iter().enumerate()
       .map(|(i, f)| {
         let ident = f.ident();
-        quote::quote! { #ident: r.get(#i).ok_or(derive_sql::Error::RowItemNotFound(#i))??  }
+        if f.sql_type().is_json() {
+          quote::quote! { #ident: derive_sql::json::try_from_value(r.get_value(#i).ok_or(derive_sql::Error::RowItemNotFound(#i))??)? }
+        } else if f.sql_type().is_custom() {
+          quote::quote! { #ident: derive_sql::traits::SqlColumnType::try_from_value(r.get_value(#i).ok_or(derive_sql::Error::RowItemNotFound(#i))??)? }
+        } else {
+          quote::quote! { #ident: r.get(#i).ok_or(derive_sql::Error::RowItemNotFound(#i))??  }
+        }
       })
+      .chain(skipped_fields.iter().map(|f| {
+        let ident = f.ident();
+        quote::quote! { #ident: ::std::default::Default::default() }
+      }))
       .collect::<Vec<proc_macro2::TokenStream>>();
       quote::quote! {
         impl<R> derive_sql::traits::TryFromRefRow<R> for #ident
@@ -282,7 +571,7 @@ impl<'a> Statement<'a> {
       quote::quote! { 
         #try_from_ref_row
         #declaration
-        impl #sql_ident { #static_members }
+        impl #sql_ident { #static_members #key_members #( #relation_members )* }
         #select_statement
       }
 
@@ -291,15 +580,47 @@ impl<'a> Statement<'a> {
         #to_params
         #try_from_ref_row
         #declaration
-        impl #sql_ident { #static_members }
+        impl #sql_ident { #static_members #key_members #( #relation_members )* }
         #table_statement
         #select_statement
         #insert_statement
         #update_statement
         #delete_statement
+        #upsert_statement
+        #returning_statement
       }
     };
     Ok(quote)
   }
 }
 
+/// Conversion of the value of the field to a statement parameter
+fn to_param(f: &fields::Fields, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+  if f.sql_type().is_json() {
+    quote::quote! { derive_sql::json::to_param(&#value)? }
+  } else if f.sql_type().is_custom() {
+    quote::quote! { derive_sql::traits::SqlColumnType::to_param(&#value)? }
+  } else {
+    quote::quote! { derive_sql::traits::ToParam::to_param(&#value)? }
+  }
+}
+
+/// Current time - bound to `now` in the generated function - assigned to `created_at` and `updated_at` fields
+fn now(f: &fields::Fields) -> proc_macro2::TokenStream {
+  match f.sql_type() {
+    SqlType::OptionDateTime => quote::quote! { Some(now) },
+    _                       => quote::quote! { now },
+  }
+}
+
+/// Value generated for the field on insert, if any
+fn on_insert_value(f: &fields::Fields) -> Option<proc_macro2::TokenStream> {
+  f.on_insert().map(|p| quote::quote! { #p() })
+  .or_else(|| if f.is_created_at() || f.is_updated_at() { Some(now(f)) } else { None })
+}
+
+/// Value generated for the field on update, if any
+fn on_update_value(f: &fields::Fields) -> Option<proc_macro2::TokenStream> {
+  f.on_update().map(|p| quote::quote! { #p() })
+  .or_else(|| if f.is_updated_at() { Some(now(f)) } else { None })
+}